@@ -0,0 +1,727 @@
+use crate::{
+    BoardError, BoardOptions, Cell, CellState, CellType, Difficulty, FirstClickPolicy, Stopwatch,
+    Vec2,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameState {
+    Playing,
+    Won,
+    Lost,
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    size_x: usize,
+    size_y: usize,
+    bomb_count: usize,
+    cells: Vec<Vec<Cell>>,
+    state: GameState,
+    first_click: FirstClickPolicy,
+    seed: u64,
+    uncovered_cells: usize,
+    stopwatch: Stopwatch,
+    paused: bool,
+    question_marks: bool,
+}
+
+impl Board {
+    /// Most cells a board can have, so a typo in a custom size can't exhaust the memory
+    pub const MAX_CELLS: usize = 1_000_000;
+
+    pub fn new(m_size_x: usize, m_size_y: usize, m_bomb_count: usize) -> Result<Board, BoardError> {
+        Board::with_options(m_size_x, m_size_y, m_bomb_count, BoardOptions::default())
+    }
+
+    pub fn with_options(
+        m_size_x: usize,
+        m_size_y: usize,
+        m_bomb_count: usize,
+        options: BoardOptions,
+    ) -> Result<Board, BoardError> {
+        Board::validate(m_size_x, m_size_y, m_bomb_count, options.first_click)?;
+
+        let seed = options.seed.unwrap_or_else(rand::random);
+
+        let c = (0..m_size_x)
+            .map(|_| (0..m_size_y).map(|_| Cell::new(CellType::Safe)).collect())
+            .collect();
+
+        let mut b = Board {
+            cells: c,
+            size_x: m_size_x,
+            size_y: m_size_y,
+            bomb_count: m_bomb_count,
+            state: GameState::Playing,
+            first_click: options.first_click,
+            seed,
+            uncovered_cells: 0,
+            stopwatch: Stopwatch::default(),
+            paused: false,
+            question_marks: options.question_marks,
+        };
+        b.place_bombs(seed);
+        b.calculate_adjacent_bombs();
+
+        Ok(b)
+    }
+
+    /// Checks the limits `Board::new` enforces without building a board
+    pub fn validate(
+        size_x: usize,
+        size_y: usize,
+        bomb_count: usize,
+        first_click: FirstClickPolicy,
+    ) -> Result<(), BoardError> {
+        if size_x == 0 || size_y == 0 {
+            return Err(BoardError::InvalidSize { size_x, size_y });
+        }
+
+        let cells = size_x
+            .checked_mul(size_y)
+            .filter(|cells| *cells <= Board::MAX_CELLS)
+            .ok_or(BoardError::TooLarge {
+                size_x,
+                size_y,
+                max_cells: Board::MAX_CELLS,
+            })?;
+
+        let max = cells.saturating_sub(first_click.reserved_cells(size_x, size_y));
+        if bomb_count > max {
+            return Err(BoardError::TooManyMines {
+                bomb_count,
+                max,
+                first_click,
+            });
+        }
+        Ok(())
+    }
+
+    // ChaCha8 rather than `StdRng`, which may change between rand releases and would give
+    // shared seeds like the daily challenge a different board on each version
+    fn place_bombs(&mut self, seed: u64) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        for _ in 0..self.bomb_count {
+            let mut x = rng.random_range(0..self.size_x);
+            let mut y = rng.random_range(0..self.size_y);
+            while let CellType::Bomb = self.cells[x][y].cell_type {
+                x = rng.random_range(0..self.size_x);
+                y = rng.random_range(0..self.size_y);
+            }
+            self.cells[x][y].cell_type = CellType::Bomb;
+        }
+    }
+
+    // Moves every mine inside `area` to the first free cells outside of it, scanning from
+    // the top left corner like the classic game does
+    fn relocate_bombs(&mut self, area: &[Vec2]) {
+        let in_area = |x: usize, y: usize| area.iter().any(|p| p.x == x && p.y == y);
+
+        for pos in area {
+            if !matches!(self.cell(pos).cell_type, CellType::Bomb) {
+                continue;
+            }
+
+            let free = (0..self.size_y)
+                .flat_map(|y| (0..self.size_x).map(move |x| Vec2 { x, y }))
+                .find(|p| !in_area(p.x, p.y) && !matches!(self.cell(p).cell_type, CellType::Bomb));
+
+            if let Some(free) = free {
+                self.cell_mut(pos).cell_type = CellType::Safe;
+                self.cell_mut(&free).cell_type = CellType::Bomb;
+            }
+        }
+
+        for cell in self.cells.iter_mut().flatten() {
+            cell.adjacent_bomb_count = 0;
+            if !matches!(cell.cell_type, CellType::Bomb) {
+                cell.cell_type = CellType::Safe;
+            }
+        }
+        self.calculate_adjacent_bombs();
+    }
+
+    fn calculate_adjacent_bombs(&mut self) {
+        for i in 0..self.size_x {
+            for j in 0..self.size_y {
+                let cell_pos = &Vec2 { x: i, y: j };
+
+                let bomb_count = self
+                    .get_adjacent_cells(cell_pos)
+                    .clone()
+                    .iter()
+                    .filter(|&i| matches!(self.cell(i).cell_type, CellType::Bomb))
+                    .count() as u8;
+
+                match bomb_count {
+                    0 => {
+                        self.cell_mut(cell_pos).cell_type = CellType::Empty;
+                    }
+                    _ => self.cell_mut(cell_pos).adjacent_bomb_count = bomb_count,
+                }
+            }
+        }
+    }
+
+    pub fn get_cell(&self, pos: &Vec2) -> Result<&Cell, BoardError> {
+        self.check_bounds(pos)?;
+        Ok(self.cell(pos))
+    }
+
+    /// Every cell, indexed as `cells()[x][y]`
+    pub fn cells(&self) -> &[Vec<Cell>] {
+        &self.cells
+    }
+
+    pub fn size_x(&self) -> usize {
+        self.size_x
+    }
+
+    pub fn size_y(&self) -> usize {
+        self.size_y
+    }
+
+    pub fn bomb_count(&self) -> usize {
+        self.bomb_count
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn flag_count(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| matches!(cell.state, CellState::Flagged))
+            .count()
+    }
+
+    /// Share of the safe cells that have been uncovered, from 0.0 to 1.0
+    pub fn progress(&self) -> f32 {
+        let safe_cells = self.size_x * self.size_y - self.bomb_count;
+
+        match safe_cells {
+            0 => 1.0,
+            _ => self.uncovered_cells.min(safe_cells) as f32 / safe_cells as f32,
+        }
+    }
+
+    /// Time since the first reveal, frozen once the game is won or lost
+    pub fn elapsed(&self) -> Duration {
+        self.stopwatch.elapsed()
+    }
+
+    /// Whether the clock is counting, i.e. something was revealed and the game isn't over
+    pub fn is_timing(&self) -> bool {
+        self.stopwatch.is_running()
+    }
+
+    /// Stops the clock without ending the game. Any move resumes it
+    pub fn pause(&mut self) {
+        if self.stopwatch.is_running() {
+            self.stopwatch.stop();
+            self.paused = true;
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.paused {
+            self.paused = false;
+            self.stopwatch.start();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn first_click(&self) -> FirstClickPolicy {
+        self.first_click
+    }
+
+    /// The seed the mines were placed with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Options that recreate this exact board with `Board::with_options`
+    pub fn options(&self) -> BoardOptions {
+        BoardOptions {
+            first_click: self.first_click,
+            seed: Some(self.seed),
+            question_marks: self.question_marks,
+        }
+    }
+
+    fn check_bounds(&self, pos: &Vec2) -> Result<(), BoardError> {
+        match pos.x < self.size_x && pos.y < self.size_y {
+            true => Ok(()),
+            false => Err(BoardError::OutOfBounds { x: pos.x, y: pos.y }),
+        }
+    }
+
+    fn cell_mut(&mut self, pos: &Vec2) -> &mut Cell {
+        &mut self.cells[pos.x][pos.y]
+    }
+
+    fn cell(&self, pos: &Vec2) -> &Cell {
+        &self.cells[pos.x][pos.y]
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::new(self.size_x, self.size_y, self.bomb_count)
+    }
+
+    /// Minimum number of clicks needed to clear the board: one per opening plus one per
+    /// numbered cell that no opening reveals
+    pub fn three_bv(&self) -> usize {
+        let mut visited = vec![vec![false; self.size_y]; self.size_x];
+        let mut count = 0;
+
+        for i in 0..self.size_x {
+            for j in 0..self.size_y {
+                if visited[i][j] || !matches!(self.cells[i][j].cell_type, CellType::Empty) {
+                    continue;
+                }
+
+                count += 1;
+                visited[i][j] = true;
+                let mut stack = vec![Vec2 { x: i, y: j }];
+
+                while let Some(pos) = stack.pop() {
+                    for adjacent in self.get_adjacent_cells(&pos) {
+                        if visited[adjacent.x][adjacent.y] {
+                            continue;
+                        }
+                        visited[adjacent.x][adjacent.y] = true;
+
+                        if let CellType::Empty = self.cell(&adjacent).cell_type {
+                            stack.push(adjacent);
+                        }
+                    }
+                }
+            }
+        }
+
+        let isolated_numbers = self
+            .cells
+            .iter()
+            .flatten()
+            .zip(visited.iter().flatten())
+            .filter(|(cell, visited)| !**visited && matches!(cell.cell_type, CellType::Safe))
+            .count();
+
+        count + isolated_numbers
+    }
+
+    fn get_adjacent_cells(&self, m_pos: &Vec2) -> Vec<Vec2> {
+        let min_x = if m_pos.x == 0 { 1 } else { 0 };
+        let min_y = if m_pos.y == 0 { 1 } else { 0 };
+
+        let max_x = if m_pos.x + 1 >= self.size_x { 2 } else { 3 };
+        let max_y = if m_pos.y + 1 >= self.size_y { 2 } else { 3 };
+
+        (min_x..max_x)
+            .flat_map(|i| {
+                (min_y..max_y).map(move |j| Vec2 {
+                    x: (m_pos.x + i - 1),
+                    y: (m_pos.y + j - 1),
+                })
+            })
+            .collect::<Vec<_>>()
+    }
+
+    /// Uncovers a hidden cell, or chords an uncovered one whose flags match its number.
+    /// Does nothing once the game is over
+    pub fn uncover(&mut self, pos: &Vec2) -> Result<(), BoardError> {
+        self.check_bounds(pos)?;
+        if self.state != GameState::Playing {
+            return Ok(());
+        }
+
+        self.resume();
+        self.uncover_cell(pos);
+        Ok(())
+    }
+
+    /// Uncovers the neighbours of an uncovered cell once it has as many adjacent flags as
+    /// its number. Unlike `uncover` it never opens a hidden cell
+    pub fn chord(&mut self, pos: &Vec2) -> Result<(), BoardError> {
+        self.check_bounds(pos)?;
+        if self.state == GameState::Playing && matches!(self.cell(pos).state, CellState::Uncovered)
+        {
+            self.resume();
+            self.uncover_cell(pos);
+        }
+        Ok(())
+    }
+
+    pub fn toggle_flag(&mut self, pos: &Vec2) -> Result<(), BoardError> {
+        self.check_bounds(pos)?;
+        if self.state == GameState::Playing {
+            self.resume();
+            let question_marks = self.question_marks;
+            self.cell_mut(pos).toggle_flagged(question_marks);
+        }
+        Ok(())
+    }
+
+    fn uncover_cell(&mut self, pos: &Vec2) {
+        let non_bomb_cells = self.size_x * self.size_y - self.bomb_count;
+
+        if self.uncovered_cells == 0
+            && matches!(
+                self.cell(pos).state,
+                CellState::Hidden | CellState::Questioned
+            )
+        {
+            self.protect_first_click(pos);
+            self.stopwatch.start();
+        }
+
+        // Cells left to uncover. Openings are flooded through this instead of recursing,
+        // which overflows the stack on big boards
+        let mut pending = match self.cell(pos).state {
+            CellState::Hidden | CellState::Questioned => vec![*pos],
+            CellState::Uncovered => {
+                let adjacent_bombs = self.cell(pos).adjacent_bomb_count;
+                let adjacent_flags = self
+                    .get_adjacent_cells(pos)
+                    .iter()
+                    .filter(|&i| matches!(self.cell(i).state, CellState::Flagged))
+                    .count() as u8;
+
+                match adjacent_bombs == adjacent_flags {
+                    true => self.get_adjacent_cells(pos),
+                    false => Vec::new(),
+                }
+            }
+            CellState::Flagged => Vec::new(),
+        };
+
+        while let Some(pos) = pending.pop() {
+            let c = self.cell_mut(&pos);
+            if !matches!(c.state, CellState::Hidden | CellState::Questioned) {
+                continue;
+            }
+
+            c.state = CellState::Uncovered;
+            match c.cell_type {
+                CellType::Bomb => {
+                    c.is_exploded = true;
+                    self.stop();
+                }
+                CellType::Empty => {
+                    pending.extend(self.get_adjacent_cells(&pos));
+                }
+                _ => {}
+            }
+            self.uncovered_cells += 1;
+        }
+
+        if let GameState::Playing = self.state
+            && self.uncovered_cells == non_bomb_cells
+        {
+            self.state = GameState::Won;
+            self.stopwatch.stop();
+        }
+    }
+
+    fn protect_first_click(&mut self, pos: &Vec2) {
+        match self.first_click {
+            FirstClickPolicy::Any => {}
+            FirstClickPolicy::Safe => self.relocate_bombs(std::slice::from_ref(pos)),
+            FirstClickPolicy::Opening => self.relocate_bombs(&self.get_adjacent_cells(pos)),
+        }
+    }
+
+    fn uncover_all(&mut self) {
+        for i in 0..self.size_x {
+            for j in 0..self.size_y {
+                let pos = &Vec2 { x: i, y: j };
+                self.cell_mut(pos).state = CellState::Uncovered;
+            }
+        }
+    }
+
+    /// Prints the board with coordinates to stdout
+    pub fn draw(&self) {
+        print!("{}", self);
+    }
+
+    fn stop(&mut self) {
+        self.state = GameState::Lost;
+        self.stopwatch.stop();
+        self.uncover_all();
+    }
+}
+
+#[cfg(feature = "color")]
+fn paint(symbol: &str, cell: &Cell) -> String {
+    use colored::Colorize;
+
+    match cell.state {
+        CellState::Hidden => symbol.white().bold().to_string(),
+        CellState::Flagged => symbol.red().to_string(),
+        CellState::Questioned => symbol.yellow().to_string(),
+        CellState::Uncovered => match (&cell.cell_type, cell.adjacent_bomb_count) {
+            (CellType::Bomb, _) => symbol.black().to_string(),
+            (CellType::Safe, 1) => symbol.blue().to_string(),
+            (CellType::Safe, 2) => symbol.green().to_string(),
+            (CellType::Safe, 3) => symbol.bright_red().dimmed().to_string(),
+            (CellType::Safe, 4) => symbol.blue().dimmed().to_string(),
+            (CellType::Safe, 5) => symbol.red().to_string(),
+            (CellType::Safe, 6) => symbol.cyan().to_string(),
+            (CellType::Safe, 7) => symbol.black().to_string(),
+            (CellType::Safe, 8) => symbol.bright_white().to_string(),
+            _ => symbol.to_string(),
+        },
+    }
+}
+
+#[cfg(not(feature = "color"))]
+fn paint(symbol: &str, _cell: &Cell) -> String {
+    symbol.to_string()
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = (self.size_x.max(self.size_y) - 1).to_string().len();
+
+        write!(f, "{:>width$}|", "")?;
+        for x in 0..self.size_x {
+            write!(f, "{:<width$} ", x)?;
+        }
+        writeln!(f)?;
+
+        for y in 0..self.size_y {
+            write!(f, "{:>width$}|", y)?;
+
+            for x in 0..self.size_x {
+                let cell = &self.cells[x][y];
+                let symbol = match (&cell.state, &cell.cell_type) {
+                    (CellState::Hidden, _) => "#".to_string(),
+                    (CellState::Flagged, _) => "F".to_string(),
+                    (CellState::Questioned, _) => "?".to_string(),
+                    (CellState::Uncovered, CellType::Bomb) => "*".to_string(),
+                    (CellState::Uncovered, CellType::Safe) => cell.adjacent_bomb_count.to_string(),
+                    (CellState::Uncovered, CellType::Empty) => ".".to_string(),
+                };
+
+                write!(f, "{}{:width$}", paint(&symbol, cell), "")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds a board from rows of `*` (mine) and `.` (safe)
+    fn layout(rows: &[&str], first_click: FirstClickPolicy) -> Board {
+        let options = BoardOptions {
+            first_click,
+            ..BoardOptions::default()
+        };
+        let mut board = Board::with_options(rows[0].len(), rows.len(), 0, options).unwrap();
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let cell = &mut board.cells[x][y];
+                cell.adjacent_bomb_count = 0;
+                cell.cell_type = match c {
+                    '*' => CellType::Bomb,
+                    _ => CellType::Safe,
+                };
+            }
+        }
+        board.bomb_count = rows.iter().map(|row| row.matches('*').count()).sum();
+        board.calculate_adjacent_bombs();
+        board
+    }
+
+    fn mines(board: &Board) -> Vec<Vec2> {
+        (0..board.size_y())
+            .flat_map(|y| (0..board.size_x()).map(move |x| Vec2::new(x, y)))
+            .filter(|pos| matches!(board.cell(pos).cell_type, CellType::Bomb))
+            .collect()
+    }
+
+    #[test]
+    fn opening_floods_a_large_board() {
+        let mut board = Board::new(500, 500, 0).unwrap();
+        board.uncover(&Vec2::new(250, 250)).unwrap();
+
+        assert_eq!(board.state(), GameState::Won);
+        assert_eq!(board.progress(), 1.0);
+    }
+
+    #[test]
+    fn validate_rejects_oversized_boards() {
+        let any = FirstClickPolicy::Any;
+
+        assert!(matches!(
+            Board::validate(usize::MAX, 2, 0, any),
+            Err(BoardError::TooLarge { .. })
+        ));
+        assert!(matches!(
+            Board::validate(100_000, 100_000, 0, any),
+            Err(BoardError::TooLarge { .. })
+        ));
+        assert_eq!(Board::validate(1000, 1000, 0, any), Ok(()));
+    }
+
+    #[test]
+    fn validate_checks_size_and_mines() {
+        use FirstClickPolicy::*;
+
+        assert_eq!(
+            Board::validate(0, 5, 0, Any),
+            Err(BoardError::InvalidSize {
+                size_x: 0,
+                size_y: 5
+            })
+        );
+        assert_eq!(Board::validate(3, 3, 9, Any), Ok(()));
+        assert_eq!(Board::validate(3, 3, 8, Safe), Ok(()));
+        assert!(matches!(
+            Board::validate(3, 3, 9, Safe),
+            Err(BoardError::TooManyMines { max: 8, .. })
+        ));
+        assert!(matches!(
+            Board::validate(3, 3, 1, Opening),
+            Err(BoardError::TooManyMines { max: 0, .. })
+        ));
+        assert_eq!(Board::validate(4, 4, 7, Opening), Ok(()));
+    }
+
+    #[test]
+    fn safe_first_click_moves_the_mine() {
+        let mut board = layout(&["*..", "...", "..."], FirstClickPolicy::Safe);
+        board.uncover(&Vec2::new(0, 0)).unwrap();
+
+        assert_eq!(board.state(), GameState::Playing);
+        assert_eq!(mines(&board), [Vec2::new(1, 0)]);
+        assert_eq!(board.cell(&Vec2::new(0, 0)).adjacent_bomb_count, 1);
+    }
+
+    #[test]
+    fn opening_first_click_clears_the_neighbours() {
+        let mut board = layout(&["*.*.", "....", "*...", "...."], FirstClickPolicy::Opening);
+        board.uncover(&Vec2::new(1, 1)).unwrap();
+
+        assert_eq!(
+            mines(&board),
+            [Vec2::new(3, 0), Vec2::new(3, 1), Vec2::new(3, 2)]
+        );
+        assert_eq!(board.cell(&Vec2::new(1, 1)).cell_type, CellType::Empty);
+        assert_eq!(board.cell(&Vec2::new(1, 1)).state, CellState::Uncovered);
+    }
+
+    #[test]
+    fn any_first_click_can_lose() {
+        let mut board = layout(&["*.", ".."], FirstClickPolicy::Any);
+        board.uncover(&Vec2::new(0, 0)).unwrap();
+
+        assert_eq!(board.state(), GameState::Lost);
+        assert!(board.cell(&Vec2::new(0, 0)).is_exploded);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_layout() {
+        let options = BoardOptions {
+            seed: Some(42),
+            ..BoardOptions::default()
+        };
+        let first = Board::with_options(30, 16, 99, options).unwrap();
+        let second = Board::with_options(30, 16, 99, options).unwrap();
+
+        assert_eq!(mines(&first).len(), 99);
+        assert_eq!(mines(&first), mines(&second));
+        assert_eq!(
+            Board::with_options(30, 16, 99, second.options())
+                .unwrap()
+                .seed(),
+            42
+        );
+    }
+
+    // Shared seeds like the daily challenge rely on the layout never changing
+    #[test]
+    fn seed_layout_is_stable() {
+        let options = BoardOptions {
+            seed: Some(1),
+            ..BoardOptions::default()
+        };
+        let board = Board::with_options(5, 5, 3, options).unwrap();
+
+        assert_eq!(
+            mines(&board),
+            [Vec2::new(4, 0), Vec2::new(1, 2), Vec2::new(2, 2)]
+        );
+    }
+
+    #[test]
+    fn three_bv_counts_openings_and_isolated_numbers() {
+        let board = layout(&["*..", "...", "..*"], FirstClickPolicy::Any);
+        assert_eq!(board.three_bv(), 2);
+
+        let board = layout(&["...", "...", "..."], FirstClickPolicy::Any);
+        assert_eq!(board.three_bv(), 1);
+
+        let board = layout(&[".*.", "*.*", ".*."], FirstClickPolicy::Any);
+        assert_eq!(board.three_bv(), 5);
+    }
+
+    #[test]
+    fn chord_needs_matching_flags() {
+        let mut board = layout(&["*..", "...", "..."], FirstClickPolicy::Any);
+        board.uncover(&Vec2::new(1, 1)).unwrap();
+
+        board.chord(&Vec2::new(1, 1)).unwrap();
+        assert!(matches!(
+            board.cell(&Vec2::new(2, 2)).state,
+            CellState::Hidden
+        ));
+
+        board.toggle_flag(&Vec2::new(0, 0)).unwrap();
+        board.chord(&Vec2::new(1, 1)).unwrap();
+        assert_eq!(board.state(), GameState::Won);
+    }
+
+    #[test]
+    fn chord_with_a_wrong_flag_loses() {
+        let mut board = layout(&["*..", "...", "..."], FirstClickPolicy::Any);
+        board.uncover(&Vec2::new(1, 1)).unwrap();
+        board.toggle_flag(&Vec2::new(0, 1)).unwrap();
+        board.chord(&Vec2::new(1, 1)).unwrap();
+
+        assert_eq!(board.state(), GameState::Lost);
+        assert!(board.cell(&Vec2::new(0, 0)).is_exploded);
+    }
+
+    #[test]
+    fn finished_games_ignore_moves() {
+        let mut board = layout(&["*.", ".."], FirstClickPolicy::Any);
+        for pos in [Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(1, 1)] {
+            board.uncover(&pos).unwrap();
+        }
+        assert_eq!(board.state(), GameState::Won);
+
+        board.toggle_flag(&Vec2::new(0, 0)).unwrap();
+        board.uncover(&Vec2::new(0, 0)).unwrap();
+
+        assert_eq!(board.state(), GameState::Won);
+        assert_eq!(board.flag_count(), 0);
+        assert!(!board.cell(&Vec2::new(0, 0)).is_exploded);
+    }
+}
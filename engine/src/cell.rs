@@ -0,0 +1,76 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec2 {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Vec2 {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellType {
+    /// Next to at least one mine
+    Safe,
+    Bomb,
+    /// No mines around, uncovering it opens its neighbours too
+    Empty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellState {
+    Hidden,
+    Uncovered,
+    Flagged,
+    /// A hidden cell the player isn't sure about. It is uncovered like any hidden cell
+    Questioned,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub(crate) state: CellState,
+    pub(crate) cell_type: CellType,
+    pub(crate) adjacent_bomb_count: u8,
+    pub(crate) is_exploded: bool,
+}
+
+impl Cell {
+    pub fn state(&self) -> CellState {
+        self.state
+    }
+
+    pub fn cell_type(&self) -> CellType {
+        self.cell_type
+    }
+
+    /// Mines in the eight cells around this one
+    pub fn adjacent_bomb_count(&self) -> u8 {
+        self.adjacent_bomb_count
+    }
+
+    /// Whether this is the mine that lost the game
+    pub fn is_exploded(&self) -> bool {
+        self.is_exploded
+    }
+
+    pub(crate) fn new(m_type: CellType) -> Cell {
+        Cell {
+            cell_type: m_type,
+            adjacent_bomb_count: 0,
+            state: CellState::Hidden,
+            is_exploded: false,
+        }
+    }
+
+    pub(crate) fn toggle_flagged(&mut self, question_marks: bool) {
+        match self.state {
+            CellState::Hidden => self.state = CellState::Flagged,
+            CellState::Flagged if question_marks => self.state = CellState::Questioned,
+            CellState::Flagged | CellState::Questioned => self.state = CellState::Hidden,
+            CellState::Uncovered => {}
+        }
+    }
+}
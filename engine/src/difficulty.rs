@@ -0,0 +1,31 @@
+/// Board dimensions and mine count, independent of any particular game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Difficulty {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub bomb_count: usize,
+}
+
+impl Difficulty {
+    pub const BEGINNER: Difficulty = Difficulty::new(8, 8, 10);
+    pub const INTERMEDIATE: Difficulty = Difficulty::new(16, 16, 40);
+    pub const EXPERT: Difficulty = Difficulty::new(30, 16, 99);
+
+    pub const fn new(size_x: usize, size_y: usize, bomb_count: usize) -> Self {
+        Self {
+            size_x,
+            size_y,
+            bomb_count,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Difficulty::BEGINNER => "Beginner",
+            Difficulty::INTERMEDIATE => "Intermediate",
+            Difficulty::EXPERT => "Expert",
+            _ => "Custom",
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::FirstClickPolicy;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardError {
+    InvalidSize {
+        size_x: usize,
+        size_y: usize,
+    },
+    TooLarge {
+        size_x: usize,
+        size_y: usize,
+        max_cells: usize,
+    },
+    TooManyMines {
+        bomb_count: usize,
+        max: usize,
+        first_click: FirstClickPolicy,
+    },
+    OutOfBounds {
+        x: usize,
+        y: usize,
+    },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::InvalidSize { size_x, size_y } => {
+                write!(f, "A {}x{} board is too small", size_x, size_y)
+            }
+            BoardError::TooLarge {
+                size_x,
+                size_y,
+                max_cells,
+            } => write!(
+                f,
+                "A {}x{} board is too large, at most {} cells are allowed",
+                size_x, size_y, max_cells
+            ),
+            BoardError::TooManyMines {
+                bomb_count,
+                max,
+                first_click,
+            } => write!(
+                f,
+                "{} mines is too many, at most {} fit with {} first clicks",
+                bomb_count,
+                max,
+                first_click.name().to_lowercase()
+            ),
+            BoardError::OutOfBounds { x, y } => write!(f, "Cell {}, {} is off the board", x, y),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
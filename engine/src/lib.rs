@@ -0,0 +1,18 @@
+//! The minesweeper rules without any user interface.
+//!
+//! A [`Board`] owns the mine layout and the player's progress. Front-ends read it through
+//! [`Board::get_cell`] and drive it with [`Board::uncover`] and [`Board::toggle_flag`].
+
+mod board;
+mod cell;
+mod difficulty;
+mod error;
+mod options;
+mod stopwatch;
+
+pub use board::{Board, GameState};
+pub use cell::{Cell, CellState, CellType, Vec2};
+pub use difficulty::Difficulty;
+pub use error::BoardError;
+pub use options::{BoardOptions, FirstClickPolicy};
+pub use stopwatch::Stopwatch;
@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// What the first uncovered cell is guaranteed to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FirstClickPolicy {
+    /// The first click can hit a mine
+    #[default]
+    Any,
+    /// A mine under the first click is moved away, like the classic game
+    Safe,
+    /// The first click and its neighbours are mine free, so it always opens an area
+    Opening,
+}
+
+impl FirstClickPolicy {
+    pub const ALL: [FirstClickPolicy; 3] = [
+        FirstClickPolicy::Any,
+        FirstClickPolicy::Safe,
+        FirstClickPolicy::Opening,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FirstClickPolicy::Any => "Unprotected",
+            FirstClickPolicy::Safe => "Safe",
+            FirstClickPolicy::Opening => "Opening",
+        }
+    }
+
+    // Cells that have to stay free of mines on a board of the given size
+    pub(crate) fn reserved_cells(&self, size_x: usize, size_y: usize) -> usize {
+        match self {
+            FirstClickPolicy::Any => 0,
+            FirstClickPolicy::Safe => 1,
+            FirstClickPolicy::Opening => size_x.min(3) * size_y.min(3),
+        }
+    }
+}
+
+impl fmt::Display for FirstClickPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BoardOptions {
+    pub first_click: FirstClickPolicy,
+    /// Boards with the same size, mine count and seed have identical layouts. A random
+    /// seed is picked when this is `None`
+    pub seed: Option<u64>,
+    /// Flagging a flagged cell marks it with a question mark instead of clearing it
+    pub question_marks: bool,
+}
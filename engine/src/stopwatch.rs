@@ -0,0 +1,32 @@
+use std::time::{Duration, Instant};
+
+/// Play time of a board. Stopping and starting again resumes from the time already measured
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stopwatch {
+    // Set while running
+    started: Option<Instant>,
+    elapsed: Duration,
+}
+
+impl Stopwatch {
+    pub fn start(&mut self) {
+        self.started.get_or_insert_with(Instant::now);
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.elapsed += started.elapsed();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.started {
+            Some(started) => self.elapsed + started.elapsed(),
+            None => self.elapsed,
+        }
+    }
+}
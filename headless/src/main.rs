@@ -0,0 +1,100 @@
+//! Plays minesweeper over stdin and stdout so bots can use the real engine.
+//!
+//! Every input line is one command, either as JSON or as words:
+//!
+//! ```text
+//! new 30 16 99 seed=42 first_click=safe   {"cmd": "new", "width": 30, "height": 16, "mines": 99, "seed": 42}
+//! open 3 4                                {"cmd": "open", "x": 3, "y": 4}
+//! flag 3 4                                {"cmd": "flag", "x": 3, "y": 4}
+//! chord 3 4                               {"cmd": "chord", "x": 3, "y": 4}
+//! state                                   {"cmd": "state"}
+//! quit                                    {"cmd": "quit"}
+//! ```
+//!
+//! Every command is answered with one JSON line holding the visible board and game status,
+//! or `"ok": false` and an `error` message.
+
+mod protocol;
+
+use minesweeper_engine::{Board, BoardOptions, FirstClickPolicy, Vec2};
+use protocol::{Command, Game, Response};
+use std::io::{self, BufRead, Write};
+
+fn new_game(
+    width: usize,
+    height: usize,
+    mines: usize,
+    seed: Option<u64>,
+    first_click: Option<&str>,
+) -> Result<Board, String> {
+    let first_click = first_click
+        .map(protocol::parse_first_click)
+        .transpose()?
+        .unwrap_or(FirstClickPolicy::default());
+
+    let options = BoardOptions {
+        first_click,
+        seed,
+        ..BoardOptions::default()
+    };
+    Board::with_options(width, height, mines, options).map_err(|e| e.to_string())
+}
+
+fn execute(game: &mut Option<Game>, command: Command) -> Response {
+    if let Command::New {
+        width,
+        height,
+        mines,
+        seed,
+        first_click,
+    } = command
+    {
+        return match new_game(width, height, mines, seed, first_click.as_deref()) {
+            Ok(board) => Response::state(game.insert(Game { board, seed })),
+            Err(e) => Response::error(e, game.as_ref()),
+        };
+    }
+
+    let Some(game) = game.as_mut() else {
+        return Response::error("no game running, send new first", None);
+    };
+
+    let board = &mut game.board;
+    let result = match command {
+        Command::Open { x, y } => board.uncover(&Vec2::new(x, y)),
+        Command::Flag { x, y } => board.toggle_flag(&Vec2::new(x, y)),
+        Command::Chord { x, y } => board.chord(&Vec2::new(x, y)),
+        Command::State => Ok(()),
+        Command::New { .. } | Command::Quit => unreachable!("handled before a board is needed"),
+    };
+
+    match result {
+        Ok(()) => Response::state(game),
+        Err(e) => Response::error(e, Some(game)),
+    }
+}
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+    let mut game = None;
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match protocol::parse_command(&line) {
+            Ok(Command::Quit) => break,
+            Ok(command) => execute(&mut game, command),
+            Err(e) => Response::error(e, game.as_ref()),
+        };
+
+        serde_json::to_writer(&mut stdout, &response)?;
+        writeln!(stdout)?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
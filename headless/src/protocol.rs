@@ -0,0 +1,256 @@
+use minesweeper_engine::{Board, Cell, CellState, CellType, FirstClickPolicy, GameState};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "lowercase")]
+pub enum Command {
+    New {
+        width: usize,
+        height: usize,
+        mines: usize,
+        #[serde(default)]
+        seed: Option<u64>,
+        #[serde(default)]
+        first_click: Option<String>,
+    },
+    Open {
+        x: usize,
+        y: usize,
+    },
+    Flag {
+        x: usize,
+        y: usize,
+    },
+    Chord {
+        x: usize,
+        y: usize,
+    },
+    State,
+    Quit,
+}
+
+pub fn parse_first_click(name: &str) -> Result<FirstClickPolicy, String> {
+    match name {
+        "any" => Ok(FirstClickPolicy::Any),
+        "safe" => Ok(FirstClickPolicy::Safe),
+        "opening" => Ok(FirstClickPolicy::Opening),
+        _ => Err(format!(
+            "unknown first click policy '{}', expected any, safe or opening",
+            name
+        )),
+    }
+}
+
+// Accepts either a JSON object (`{"cmd": "open", "x": 3, "y": 4}`) or the same command
+// written out as words (`open 3 4`)
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        return serde_json::from_str(line).map_err(|e| e.to_string());
+    }
+
+    let mut words = line.split_whitespace();
+    let name = words.next().ok_or("empty command")?;
+    let args = words.collect::<Vec<_>>();
+
+    let number = |i: usize| -> Result<usize, String> {
+        let arg = args
+            .get(i)
+            .ok_or(format!("{} is missing argument {}", name, i + 1))?;
+        arg.parse()
+            .map_err(|_| format!("'{}' is not a number", arg))
+    };
+
+    match name {
+        "new" => {
+            let mut seed = None;
+            let mut first_click = None;
+
+            for option in args.iter().skip(3) {
+                match option.split_once('=') {
+                    Some(("seed", value)) => {
+                        seed = Some(
+                            value
+                                .parse()
+                                .map_err(|_| format!("'{}' is not a valid seed", value))?,
+                        )
+                    }
+                    Some(("first_click", value)) => first_click = Some(value.to_string()),
+                    _ => return Err(format!("unknown option '{}'", option)),
+                }
+            }
+
+            Ok(Command::New {
+                width: number(0)?,
+                height: number(1)?,
+                mines: number(2)?,
+                seed,
+                first_click,
+            })
+        }
+        "open" => Ok(Command::Open {
+            x: number(0)?,
+            y: number(1)?,
+        }),
+        "flag" => Ok(Command::Flag {
+            x: number(0)?,
+            y: number(1)?,
+        }),
+        "chord" => Ok(Command::Chord {
+            x: number(0)?,
+            y: number(1)?,
+        }),
+        "state" => Ok(Command::State),
+        "quit" => Ok(Command::Quit),
+        _ => Err(format!("unknown command '{}'", name)),
+    }
+}
+
+// A running game. `seed` is only set when the client picked it, echoing one the engine
+// picked would let a bot rebuild the board with it and read the mines
+pub struct Game {
+    pub board: Board,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BoardState {
+    pub status: &'static str,
+    pub width: usize,
+    pub height: usize,
+    pub mines: usize,
+    pub flags: usize,
+    pub seed: Option<u64>,
+    // One string per row: `#` hidden, `F` flagged, `?` question mark, `0`-`8` uncovered,
+    // `*` mine, `X` the mine that was hit. Mines are only shown once the game is lost
+    pub board: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub state: Option<BoardState>,
+}
+
+impl Response {
+    pub fn state(game: &Game) -> Self {
+        Self {
+            ok: true,
+            error: None,
+            state: Some(board_state(game)),
+        }
+    }
+
+    pub fn error(error: impl ToString, game: Option<&Game>) -> Self {
+        Self {
+            ok: false,
+            error: Some(error.to_string()),
+            state: game.map(board_state),
+        }
+    }
+}
+
+fn cell_symbol(cell: &Cell) -> char {
+    match cell.state() {
+        CellState::Hidden => '#',
+        CellState::Flagged => 'F',
+        CellState::Questioned => '?',
+        CellState::Uncovered => match cell.cell_type() {
+            CellType::Bomb if cell.is_exploded() => 'X',
+            CellType::Bomb => '*',
+            CellType::Safe => char::from(b'0' + cell.adjacent_bomb_count()),
+            CellType::Empty => '0',
+        },
+    }
+}
+
+fn board_state(game: &Game) -> BoardState {
+    let board = &game.board;
+    let rows = (0..board.size_y())
+        .map(|y| {
+            (0..board.size_x())
+                .map(|x| cell_symbol(&board.cells()[x][y]))
+                .collect()
+        })
+        .collect();
+
+    BoardState {
+        status: match board.state() {
+            GameState::Playing => "playing",
+            GameState::Won => "won",
+            GameState::Lost => "lost",
+        },
+        width: board.size_x(),
+        height: board.size_y(),
+        mines: board.bomb_count(),
+        flags: board.flag_count(),
+        seed: game.seed,
+        board: rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_and_json_parse_the_same() {
+        assert_eq!(parse_command("open 3 4"), Ok(Command::Open { x: 3, y: 4 }));
+        assert_eq!(
+            parse_command(r#" {"cmd": "open", "x": 3, "y": 4} "#),
+            Ok(Command::Open { x: 3, y: 4 })
+        );
+        assert_eq!(parse_command("state"), Ok(Command::State));
+    }
+
+    #[test]
+    fn new_takes_options() {
+        assert_eq!(
+            parse_command("new 30 16 99 seed=42 first_click=safe"),
+            Ok(Command::New {
+                width: 30,
+                height: 16,
+                mines: 99,
+                seed: Some(42),
+                first_click: Some("safe".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_command("new 9 9 10"),
+            Ok(Command::New {
+                width: 9,
+                height: 9,
+                mines: 10,
+                seed: None,
+                first_click: None,
+            })
+        );
+    }
+
+    #[test]
+    fn bad_commands_are_errors() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command("dig 1 2").is_err());
+        assert!(parse_command("open 1").is_err());
+        assert!(parse_command("flag x 2").is_err());
+        assert!(parse_command("new 9 9 10 seed=abc").is_err());
+        assert!(parse_command("new 9 9 10 colour=red").is_err());
+        assert!(parse_command(r#"{"cmd": "open"}"#).is_err());
+    }
+
+    #[test]
+    fn only_supplied_seeds_are_echoed() {
+        let board = Board::new(9, 9, 10).unwrap();
+        let game = Game { board, seed: None };
+        assert_eq!(board_state(&game).seed, None);
+
+        let game = Game {
+            seed: Some(7),
+            ..game
+        };
+        assert_eq!(board_state(&game).seed, Some(7));
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::AppError;
+use crate::globals;
+use crate::input::Bindings;
+use crate::resources;
+use crate::storage;
+use crate::themes;
+use iced::Theme;
+use minesweeper_engine::{BoardOptions, Difficulty, FirstClickPolicy};
+use serde::{Deserialize, Serialize};
+use std::{fs, io};
+
+const CONFIG_FILE: &str = "config.toml";
+
+// Settings changed in the app are written back here, but the file can be edited by hand
+// too. Every value is optional, missing ones fall back to defaults. Flagging with the left
+// button and revealing with the right one looks like this:
+//
+// [bindings.mouse]
+// left = "flag"
+// right = "reveal"
+// middle = "chord"
+// back = "chord"
+//
+// [bindings.mouse.other]
+// 8 = "flag"
+//
+// [bindings.keys]
+// flag = ["f", "Shift"]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub first_click: FirstClickPolicy,
+    pub question_marks: bool,
+    // Physical screen pixels per sprite pixel. `None` picks one from the monitor's scale
+    // factor
+    pub scale: Option<u16>,
+    // "Classic" or one of the names iced gives its themes, like "Tokyo Night"
+    pub theme: String,
+    pub skin: String,
+    // Size of the last game started, the next launch opens with it
+    pub difficulty: Difficulty,
+    pub bindings: Bindings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            first_click: FirstClickPolicy::default(),
+            question_marks: false,
+            scale: None,
+            theme: Theme::TokyoNight.to_string(),
+            skin: resources::DEFAULT_SKIN.to_string(),
+            difficulty: Difficulty::BEGINNER,
+            bindings: Bindings::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self, AppError> {
+        let Some(path) = storage::config_path(CONFIG_FILE) else {
+            return Ok(Self::default());
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+
+        toml::from_str(&contents).map_err(|e| storage::set_aside(&path, e))
+    }
+
+    pub fn save(&self) -> Result<(), AppError> {
+        self.write()
+            .map_err(|e| AppError::Save(format!("{}: {}", CONFIG_FILE, e)))
+    }
+
+    fn write(&self) -> io::Result<()> {
+        let Some(path) = storage::config_path(CONFIG_FILE) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(
+            path,
+            toml::to_string_pretty(self).map_err(io::Error::other)?,
+        )
+    }
+
+    // Options for a new game picked by the player
+    pub fn board_options(&self) -> BoardOptions {
+        BoardOptions {
+            first_click: self.first_click,
+            question_marks: self.question_marks,
+            ..BoardOptions::default()
+        }
+    }
+
+    // Unknown names, say from a newer version, get the default theme
+    pub fn theme(&self) -> Theme {
+        themes::named(&self.theme).unwrap_or(Theme::TokyoNight)
+    }
+
+    // The automatic scale keeps the default size in logical pixels, rounded to whole
+    // physical pixels
+    pub fn scale(&self, scale_factor: f32) -> u16 {
+        self.scale
+            .unwrap_or_else(|| (globals::DEFAULT_SCALE as f32 * scale_factor).round() as u16)
+            .clamp(globals::MIN_SCALE, globals::MAX_SCALE)
+    }
+}
@@ -0,0 +1,199 @@
+use crate::messages::BoardMessage;
+use crate::presets::Presets;
+use iced::widget::{
+    Column, button, column, container, pick_list, row, scrollable, slider, text, text_input,
+};
+use iced::{Element, Length, Theme};
+use minesweeper_engine::{Board, Difficulty, FirstClickPolicy};
+
+#[derive(Debug, Clone)]
+pub struct CustomGameForm {
+    pub width: String,
+    pub height: String,
+    pub mines: String,
+    pub preset_name: String,
+}
+
+impl CustomGameForm {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            width: difficulty.size_x.to_string(),
+            height: difficulty.size_y.to_string(),
+            mines: difficulty.bomb_count.to_string(),
+            preset_name: String::new(),
+        }
+    }
+
+    pub fn difficulty(&self, first_click: FirstClickPolicy) -> Result<Difficulty, String> {
+        let parse = |value: &str, field: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("{} must be a whole number", field))
+        };
+
+        let difficulty = Difficulty::new(
+            parse(&self.width, "Width")?,
+            parse(&self.height, "Height")?,
+            parse(&self.mines, "Mines")?,
+        );
+
+        Board::validate(
+            difficulty.size_x,
+            difficulty.size_y,
+            difficulty.bomb_count,
+            first_click,
+        )
+        .map_err(|e| e.to_string())?;
+
+        Ok(difficulty)
+    }
+
+    // Mine density in percent, if width and height are valid
+    pub fn density(&self) -> Option<f32> {
+        let cells = self
+            .width
+            .trim()
+            .parse::<usize>()
+            .ok()?
+            .checked_mul(self.height.trim().parse::<usize>().ok()?)?;
+        let mines = self.mines.trim().parse::<usize>().unwrap_or(0);
+
+        match cells {
+            0 => None,
+            _ => Some(mines as f32 / cells as f32 * 100.0),
+        }
+    }
+
+    pub fn set_density(&mut self, density: f32) {
+        let (Ok(width), Ok(height)) = (
+            self.width.trim().parse::<usize>(),
+            self.height.trim().parse::<usize>(),
+        ) else {
+            return;
+        };
+        let Some(cells) = width.checked_mul(height) else {
+            return;
+        };
+
+        let mines = cells as f32 * density / 100.0;
+        self.mines = (mines.round() as usize).to_string();
+    }
+}
+
+fn error_style(theme: &Theme) -> text::Style {
+    text::Style {
+        color: Some(theme.extended_palette().danger.base.color),
+    }
+}
+
+fn labelled_input<'a>(
+    label: &'a str,
+    value: &'a str,
+    on_input: fn(String) -> BoardMessage,
+) -> Element<'a, BoardMessage> {
+    row![
+        text(label).width(80),
+        text_input("", value).on_input(on_input).width(Length::Fill),
+    ]
+    .spacing(8)
+    .into()
+}
+
+pub fn custom_game_element<'a>(
+    form: &'a CustomGameForm,
+    presets: &'a Presets,
+    first_click: FirstClickPolicy,
+) -> Element<'a, BoardMessage> {
+    let difficulty = form.difficulty(first_click);
+
+    let density = form.density();
+    let density_row = row![
+        text("Density").width(80),
+        slider(
+            1.0..=99.0,
+            density.unwrap_or(1.0),
+            BoardMessage::CustomDensityChanged
+        )
+        .step(1.0),
+        text(density.map_or("-".to_string(), |d| format!("{:.0}%", d))).width(40),
+    ]
+    .spacing(8);
+
+    let status: Element<'a, BoardMessage> = match &difficulty {
+        Ok(d) => text(format!(
+            "{}x{} with {} mines",
+            d.size_x, d.size_y, d.bomb_count
+        ))
+        .into(),
+        Err(e) => text(e.clone()).style(error_style).into(),
+    };
+
+    let play = button("Play").on_press_maybe(
+        difficulty
+            .as_ref()
+            .ok()
+            .map(|d| BoardMessage::SubmitNewGame(d.size_x, d.size_y, d.bomb_count)),
+    );
+
+    let can_save = difficulty.is_ok() && !form.preset_name.trim().is_empty();
+    let save_row = row![
+        text_input("Preset name", &form.preset_name)
+            .on_input(BoardMessage::CustomPresetNameChanged)
+            .width(Length::Fill),
+        button("Save preset").on_press_maybe(can_save.then_some(BoardMessage::SavePreset)),
+    ]
+    .spacing(8);
+
+    let preset_list = presets.presets.iter().map(|preset| {
+        row![
+            text(format!(
+                "{} ({}x{}, {})",
+                preset.name, preset.size_x, preset.size_y, preset.bomb_count
+            ))
+            .width(Length::Fill),
+            button("Play").on_press(BoardMessage::SubmitNewGame(
+                preset.size_x,
+                preset.size_y,
+                preset.bomb_count
+            )),
+            button("Delete")
+                .style(button::danger)
+                .on_press(BoardMessage::DeletePreset(preset.name.clone())),
+        ]
+        .spacing(8)
+        .into()
+    });
+
+    container(
+        column![
+            row![
+                text("Custom game").size(24).width(Length::Fill),
+                button("Back").on_press(BoardMessage::OpenNewGameModal),
+            ],
+            labelled_input("Width", &form.width, BoardMessage::CustomWidthChanged),
+            labelled_input("Height", &form.height, BoardMessage::CustomHeightChanged),
+            labelled_input("Mines", &form.mines, BoardMessage::CustomMinesChanged),
+            density_row,
+            row![
+                text("First click").width(80),
+                pick_list(
+                    FirstClickPolicy::ALL,
+                    Some(first_click),
+                    BoardMessage::FirstClickChanged
+                ),
+            ]
+            .spacing(8),
+            status,
+            play,
+            save_row,
+            text("Presets").size(20),
+            scrollable(Column::with_children(preset_list).spacing(4)),
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
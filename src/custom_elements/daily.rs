@@ -0,0 +1,75 @@
+use crate::daily::{self, Challenge, DailyHistory, DailyResult};
+use crate::messages::BoardMessage;
+use crate::stats;
+use iced::widget::{Column, button, column, container, row, scrollable, text};
+use iced::{Element, Length};
+
+const RECENT_DAYS: usize = 14;
+
+fn outcome(result: &DailyResult) -> String {
+    match (result.won, result.time_ms) {
+        (true, Some(time_ms)) => format!("Cleared in {}", stats::format_time(time_ms)),
+        (false, Some(_)) => "Lost".to_string(),
+        (_, None) => "Not finished".to_string(),
+    }
+}
+
+pub fn daily_element(
+    history: &DailyHistory,
+    today: u64,
+    can_play: bool,
+) -> Element<'static, BoardMessage> {
+    let challenge = Challenge::of(today);
+    let difficulty = challenge.difficulty;
+
+    let (status, play) = match history.result(today) {
+        Some(result) => (outcome(result), None),
+        None if !can_play => ("Leave the multiplayer game to play".to_string(), None),
+        None => (
+            "One attempt, make it count".to_string(),
+            Some(BoardMessage::PlayDaily),
+        ),
+    };
+
+    let recent = history
+        .results
+        .iter()
+        .rev()
+        .take(RECENT_DAYS)
+        .map(|result| {
+            text(format!(
+                "{}: {}",
+                daily::format_day(result.day),
+                outcome(result)
+            ))
+            .into()
+        });
+
+    container(
+        column![
+            row![
+                text("Daily challenge").size(24).width(Length::Fill),
+                button("Back").on_press(BoardMessage::CloseScreen),
+            ],
+            text(format!(
+                "{}: {}, {}x{} with {} mines",
+                daily::format_day(today),
+                difficulty.name(),
+                difficulty.size_x,
+                difficulty.size_y,
+                difficulty.bomb_count
+            )),
+            text(status),
+            button("Play").on_press_maybe(play),
+            text(format!("Current streak: {}", history.current_streak(today))),
+            text(format!("Best streak: {}", history.best_streak())),
+            text("Recent results").size(20),
+            scrollable(Column::with_children(recent).spacing(4)).height(Length::Fill),
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -0,0 +1,30 @@
+use crate::error::AppError;
+use crate::messages::BoardMessage;
+use iced::widget::{button, container, row, text};
+use iced::{Element, Length, Theme};
+
+fn error_banner_style(theme: &Theme) -> container::Style {
+    let danger = theme.extended_palette().danger.base;
+
+    container::Style {
+        background: Some(danger.color.into()),
+        text_color: Some(danger.text),
+        ..container::Style::default()
+    }
+}
+
+pub fn error_banner_element(error: &AppError) -> Element<'static, BoardMessage> {
+    container(
+        row![
+            text(error.to_string()).width(Length::Fill),
+            button("Dismiss")
+                .style(button::secondary)
+                .on_press(BoardMessage::DismissError),
+        ]
+        .spacing(8),
+    )
+    .padding(8)
+    .width(Length::Fill)
+    .style(error_banner_style)
+    .into()
+}
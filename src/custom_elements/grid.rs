@@ -1,58 +1,295 @@
 use crate::globals;
+use crate::input::{MouseAction, MouseBindings};
 use crate::messages::BoardMessage;
-use crate::minesweeper::{Board, CellPressedState, CellState, CellType, Vec2};
-use crate::resources;
-use iced::Element;
+use crate::resources::Skin;
+use iced::advanced::image::{self, Image};
+use iced::advanced::layout::{self, Layout};
+use iced::advanced::renderer;
+use iced::advanced::widget::{self, Widget, tree};
+use iced::advanced::{Clipboard, Shell};
 use iced::widget::image::FilterMethod;
-use iced::widget::{Column, Row, container, image, mouse_area};
+use iced::{
+    Border, Color, Element, Event, Length, Point, Rectangle, Size, Theme, color, event, mouse,
+};
+use minesweeper_engine::{Board, CellState, CellType, Vec2};
+use std::collections::HashMap;
 
-pub fn cell_grid_element(board: &Board) -> Element<'_, BoardMessage> {
-    let mut grid = Row::new();
+const PLAYER_COLOURS: [Color; 4] = [
+    color!(0xff5555),
+    color!(0x50fa7b),
+    color!(0xf1fa8c),
+    color!(0xbd93f9),
+];
 
-    for row in 0..board.size_x {
-        let mut column_element: Column<BoardMessage> = Column::new();
+// Indexed by the number of adjacent mines
+const NUMBER_SPRITES: [&str; 9] = [
+    "empty.png",
+    "1.png",
+    "2.png",
+    "3.png",
+    "4.png",
+    "5.png",
+    "6.png",
+    "7.png",
+    "8.png",
+];
 
-        for column in 0..board.size_y {
-            let pos = Vec2 { x: row, y: column };
-            let cell = board.get_cell(&pos);
+fn sprite(board: &Board, pos: Vec2, state: &State) -> &'static str {
+    let cell = &board.cells()[pos.x][pos.y];
 
-            let image_name = match cell.state {
-                CellState::Hidden => match cell.pressed_state {
-                    CellPressedState::None => "hidden",
-                    CellPressedState::Hovered => "hidden-hovered",
-                    CellPressedState::Pressed => "hidden-pressed",
-                },
-                CellState::Flagged => "flag",
-                CellState::Uncovered => match cell.cell_type {
-                    CellType::Bomb => match cell.is_exploded {
-                        false => "bomb",
-                        true => "bomb-exploded",
+    match cell.state() {
+        CellState::Hidden if state.hovered != Some(pos) => "hidden.png",
+        CellState::Hidden => match state.pressed {
+            Some((_, pressed)) if pressed == pos => "hidden-pressed.png",
+            _ => "hidden-hovered.png",
+        },
+        CellState::Flagged => "flag.png",
+        CellState::Questioned => "question.png",
+        CellState::Uncovered => match cell.cell_type() {
+            CellType::Bomb => match cell.is_exploded() {
+                false => "bomb.png",
+                true => "bomb-exploded.png",
+            },
+            CellType::Safe => NUMBER_SPRITES[cell.adjacent_bomb_count() as usize],
+            CellType::Empty => "empty.png",
+        },
+    }
+}
+
+// Hover and press are only for show, so they live here instead of in the board
+#[derive(Debug, Default)]
+struct State {
+    hovered: Option<Vec2>,
+    // The cell a reveal button went down on. Releasing it over another cell does nothing
+    pressed: Option<(mouse::Button, Vec2)>,
+}
+
+// The whole board as one widget. It draws the cells straight from the board and works out
+// which cell the mouse is on itself, so the app only hears about the cells being played
+struct Grid<'a> {
+    board: &'a Board,
+    cursor: Option<Vec2>,
+    cursors: Option<&'a HashMap<usize, Vec2>>,
+    cell_size: f32,
+    pixel_size: f32,
+    skin: &'a Skin,
+    bindings: &'a MouseBindings,
+    // Presses drag the board instead while Space is held
+    dragging: bool,
+}
+
+impl Grid<'_> {
+    fn board_size(&self) -> Size {
+        Size::new(
+            self.board.size_x() as f32 * self.cell_size,
+            self.board.size_y() as f32 * self.cell_size,
+        )
+    }
+
+    fn cell_at(&self, bounds: Rectangle, position: Point) -> Option<Vec2> {
+        if !bounds.contains(position) {
+            return None;
+        }
+        let x = ((position.x - bounds.x) / self.cell_size) as usize;
+        let y = ((position.y - bounds.y) / self.cell_size) as usize;
+
+        (x < self.board.size_x() && y < self.board.size_y()).then_some(Vec2::new(x, y))
+    }
+
+    fn cell_bounds(&self, bounds: Rectangle, pos: Vec2) -> Rectangle {
+        Rectangle::new(
+            Point::new(
+                bounds.x + pos.x as f32 * self.cell_size,
+                bounds.y + pos.y as f32 * self.cell_size,
+            ),
+            Size::new(self.cell_size, self.cell_size),
+        )
+    }
+
+    // `None` is this player's keyboard cursor, `cursors` holds where the other players in
+    // a multiplayer game are pointing
+    fn highlights(&self) -> impl Iterator<Item = (Vec2, Option<usize>)> + '_ {
+        let others = self
+            .cursors
+            .into_iter()
+            .flatten()
+            .map(|(player, pos)| (*pos, Some(*player)));
+
+        self.cursor.map(|pos| (pos, None)).into_iter().chain(others)
+    }
+}
+
+impl<Renderer> Widget<BoardMessage, Theme, Renderer> for Grid<'_>
+where
+    Renderer: image::Renderer<Handle = image::Handle>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        let size = self.board_size();
+        Size::new(Length::Fixed(size.width), Length::Fixed(size.height))
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut widget::Tree,
+        _renderer: &Renderer,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(self.board_size())
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let Some(visible) = bounds.intersection(viewport) else {
+            return;
+        };
+
+        // Only the cells in view are drawn, most of a huge board is scrolled away
+        let first = |start: f32, origin: f32| ((start - origin) / self.cell_size) as usize;
+        let last = |end: f32, origin: f32, count: usize| {
+            (((end - origin) / self.cell_size).ceil() as usize).min(count)
+        };
+        let columns = first(visible.x, bounds.x)
+            ..last(visible.x + visible.width, bounds.x, self.board.size_x());
+        let rows = first(visible.y, bounds.y)
+            ..last(visible.y + visible.height, bounds.y, self.board.size_y());
+
+        for x in columns {
+            for y in rows.clone() {
+                let pos = Vec2::new(x, y);
+
+                renderer.draw_image(
+                    Image::new(self.skin.get_image_handle(sprite(self.board, pos, state)))
+                        .filter_method(FilterMethod::Nearest)
+                        .snap(true),
+                    self.cell_bounds(bounds, pos),
+                );
+            }
+        }
+
+        for (pos, player) in self.highlights() {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: self.cell_bounds(bounds, pos),
+                    border: Border {
+                        color: match player {
+                            Some(player) => PLAYER_COLOURS[player % PLAYER_COLOURS.len()],
+                            None => theme.palette().primary,
+                        },
+                        width: self.pixel_size,
+                        radius: 0.0.into(),
                     },
-                    CellType::Safe => &cell.adjacent_bomb_count.to_string(),
-                    CellType::Empty(..) => "empty",
+                    ..renderer::Quad::default()
                 },
-            };
+                Color::TRANSPARENT,
+            );
+        }
+    }
 
-            let cell_element: Element<'_, BoardMessage> = container(
-                mouse_area(
-                    image(resources::get_image_handle(format!("{}.png", image_name)))
-                        .filter_method(FilterMethod::Nearest)
-                        .height(globals::SCALE)
-                        .width(globals::SCALE),
-                )
-                .on_enter(BoardMessage::CellHover(row, column))
-                .on_exit(BoardMessage::CellUnhover(row, column))
-                .on_press(BoardMessage::CellPress(row, column))
-                .on_release(BoardMessage::CellLeftClick(row, column))
-                .on_right_release(BoardMessage::CellRightClick(row, column)),
-            )
-            .height(globals::SCALE)
-            .width(globals::SCALE)
-            .into();
-
-            column_element = column_element.push(cell_element);
+    fn on_event(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, BoardMessage>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let hovered = cursor
+            .position()
+            .and_then(|position| self.cell_at(layout.bounds(), position));
+
+        if hovered != state.hovered {
+            state.hovered = hovered;
+
+            // Only the other players need to know where the mouse is
+            if self.cursors.is_some() {
+                shell.publish(BoardMessage::CellPointed(hovered));
+            }
+        }
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(button)) if !self.dragging => {
+                let Some(pos) = hovered else {
+                    return event::Status::Ignored;
+                };
+
+                // Only the reveal button shows the cell pushed in, like the classic game
+                if self.bindings.action(button) == MouseAction::Reveal {
+                    state.pressed = Some((button, pos));
+                }
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(button)) => {
+                // The press is let go of wherever the mouse is
+                let pressed = match state.pressed {
+                    Some((pressed, pos)) if pressed == button => {
+                        state.pressed = None;
+                        Some(pos)
+                    }
+                    _ => None,
+                };
+                let Some(pos) = hovered else {
+                    return event::Status::Ignored;
+                };
+
+                if self.bindings.action(button) != MouseAction::Reveal || pressed == Some(pos) {
+                    shell.publish(BoardMessage::CellRelease(button, pos.x, pos.y));
+                }
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
         }
-        grid = grid.push(column_element);
     }
-    grid.into()
+}
+
+impl<'a, Renderer> From<Grid<'a>> for Element<'a, BoardMessage, Theme, Renderer>
+where
+    Renderer: image::Renderer<Handle = image::Handle> + 'a,
+{
+    fn from(grid: Grid<'a>) -> Self {
+        Self::new(grid)
+    }
+}
+
+pub fn cell_grid_element<'a>(
+    board: &'a Board,
+    cursor: Option<Vec2>,
+    cursors: Option<&'a HashMap<usize, Vec2>>,
+    pixel_size: f32,
+    skin: &'a Skin,
+    bindings: &'a MouseBindings,
+    dragging: bool,
+) -> Element<'a, BoardMessage> {
+    Grid {
+        board,
+        cursor,
+        cursors,
+        cell_size: globals::cell_size(pixel_size),
+        pixel_size,
+        skin,
+        bindings,
+        dragging,
+    }
+    .into()
 }
@@ -0,0 +1,142 @@
+use crate::messages::BoardMessage;
+use iced::widget::image::{FilterMethod, Handle};
+use iced::widget::scrollable::Viewport;
+use iced::widget::{Space, container, image, mouse_area, stack};
+use iced::{Border, Color, Element, Padding, Point, Theme};
+use minesweeper_engine::{Board, Cell, CellState, CellType};
+
+// Longest side of the minimap in logical pixels, unless the board needs more than a
+// pixel per cell
+const MAX_SIZE: f32 = 160.0;
+const MAX_CELL_SIZE: f32 = 4.0;
+
+fn cell_colour(cell: &Cell) -> [u8; 3] {
+    match cell.state() {
+        CellState::Hidden => [0x82, 0x82, 0x82],
+        CellState::Flagged => [0xe0, 0x30, 0x30],
+        CellState::Questioned => [0xe0, 0xc0, 0x30],
+        CellState::Uncovered => match cell.cell_type() {
+            CellType::Bomb => match cell.is_exploded() {
+                false => [0x10, 0x10, 0x10],
+                true => [0xff, 0x00, 0x00],
+            },
+            // Darker the more mines are around
+            CellType::Safe => {
+                let shade = 0xd8 - cell.adjacent_bomb_count() * 0x14;
+                [shade, shade, 0xd8]
+            }
+            CellType::Empty => [0xd8, 0xd8, 0xd8],
+        },
+    }
+}
+
+// One pixel per cell, `x` across like the board
+fn pixels(board: &Board) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(board.size_x() * board.size_y() * 4);
+
+    for y in 0..board.size_y() {
+        for x in 0..board.size_x() {
+            let [r, g, b] = cell_colour(&board.cells()[x][y]);
+            pixels.extend_from_slice(&[r, g, b, 0xff]);
+        }
+    }
+    pixels
+}
+
+// The board drawn one pixel per cell. Rebuilt only when the board changes, the renderer
+// uploads the image again for every new handle
+pub fn handle(board: &Board) -> Handle {
+    Handle::from_rgba(board.size_x() as u32, board.size_y() as u32, pixels(board))
+}
+
+// Logical pixels per cell on the minimap
+pub fn cell_size(board: &Board) -> f32 {
+    let longest = board.size_x().max(board.size_y()) as f32;
+    (MAX_SIZE / longest).floor().clamp(1.0, MAX_CELL_SIZE)
+}
+
+fn outline_style(theme: &Theme) -> container::Style {
+    container::Style {
+        border: Border {
+            color: theme.palette().primary,
+            width: 1.0,
+            radius: 0.0.into(),
+        },
+        ..container::Style::default()
+    }
+}
+
+fn background_style(theme: &Theme) -> container::Style {
+    let background = theme.extended_palette().background;
+
+    container::Style {
+        border: Border {
+            color: background.strong.color,
+            width: 1.0,
+            radius: 0.0.into(),
+        },
+        background: Some(
+            Color {
+                a: 0.8,
+                ..background.base.color
+            }
+            .into(),
+        ),
+        ..container::Style::default()
+    }
+}
+
+// `handle` is the board from `handle`, made once per change. `board_cell_size` is the
+// size of a cell in the viewport, to work out how much of the board it shows
+pub fn minimap_element(
+    handle: Handle,
+    board: &Board,
+    viewport: Viewport,
+    board_cell_size: f32,
+) -> Element<'static, BoardMessage> {
+    let cell_size = cell_size(board);
+    let (width, height) = (
+        board.size_x() as f32 * cell_size,
+        board.size_y() as f32 * cell_size,
+    );
+
+    // The visible part of the board, in minimap pixels
+    let ratio = cell_size / board_cell_size;
+    let offset = viewport.absolute_offset();
+    let bounds = viewport.bounds();
+    let left = (offset.x * ratio).min(width);
+    let top = (offset.y * ratio).min(height);
+    let outline = container(Space::new(
+        (bounds.width * ratio).min(width - left),
+        (bounds.height * ratio).min(height - top),
+    ))
+    .style(outline_style);
+
+    let map = image(handle)
+        .filter_method(FilterMethod::Nearest)
+        .width(width)
+        .height(height);
+
+    container(
+        mouse_area(stack![
+            map,
+            container(outline).padding(Padding {
+                top,
+                left,
+                ..Padding::ZERO
+            })
+        ])
+        .on_move(BoardMessage::MinimapMoved)
+        .on_press(BoardMessage::MinimapPressed)
+        .on_release(BoardMessage::MinimapReleased),
+    )
+    .padding(1)
+    .style(background_style)
+    .into()
+}
+
+// The cell under a point on the minimap
+pub fn cell_at(board: &Board, position: Point) -> Point {
+    let cell_size = cell_size(board);
+    Point::new(position.x / cell_size, position.y / cell_size)
+}
@@ -1,19 +1,135 @@
+mod custom_game;
+mod daily;
+mod error_banner;
 mod grid;
+mod minimap;
+mod multiplayer;
 mod new_game;
+mod paused;
+mod race;
+mod settings;
+mod seven_segment;
+mod statistics;
 mod top_bar;
+mod viewport;
 
 use crate::BoardMessage;
-use crate::minesweeper::{Board, GameState};
-use iced::Element;
+use crate::config::Config;
+use crate::daily::DailyHistory;
+use crate::error::AppError;
+use crate::input::MouseBindings;
+use crate::network::{Mode, Progress, Session};
+use crate::presets::Presets;
+use crate::resources::Skin;
+use crate::stats::History;
+use iced::widget::scrollable::Viewport;
+use iced::widget::{image, scrollable};
+use iced::{Element, Point};
+use minesweeper_engine::{Board, Difficulty, FirstClickPolicy, GameState, Vec2};
+use std::collections::HashMap;
 
-pub fn grid(board: &Board) -> Element<'_, BoardMessage> {
-    grid::cell_grid_element(&board)
+pub use custom_game::CustomGameForm;
+pub use settings::{SettingsForm, parse_keys};
+
+pub fn grid<'a>(
+    board: &'a Board,
+    cursor: Option<Vec2>,
+    cursors: Option<&'a HashMap<usize, Vec2>>,
+    pixel_size: f32,
+    skin: &'a Skin,
+    bindings: &'a MouseBindings,
+    dragging: bool,
+) -> Element<'a, BoardMessage> {
+    grid::cell_grid_element(board, cursor, cursors, pixel_size, skin, bindings, dragging)
+}
+
+pub fn viewport(board: Element<'_, BoardMessage>) -> Element<'_, BoardMessage> {
+    viewport::viewport_element(board)
+}
+
+pub fn viewport_id() -> scrollable::Id {
+    viewport::id()
+}
+
+pub fn minimap(
+    handle: image::Handle,
+    board: &Board,
+    viewport: Viewport,
+    board_cell_size: f32,
+) -> Element<'static, BoardMessage> {
+    minimap::minimap_element(handle, board, viewport, board_cell_size)
+}
+
+pub fn minimap_image(board: &Board) -> image::Handle {
+    minimap::handle(board)
+}
+
+pub fn minimap_cell(board: &Board, position: Point) -> Point {
+    minimap::cell_at(board, position)
+}
+
+pub fn paused(size_x: usize, size_y: usize, pixel_size: f32) -> Element<'static, BoardMessage> {
+    paused::paused_element(size_x, size_y, pixel_size)
+}
+
+pub fn top_bar(
+    game_state: GameState,
+    mines_left: isize,
+    time: usize,
+    pixel_size: f32,
+    skin: &Skin,
+) -> Element<'static, BoardMessage> {
+    top_bar::top_bar_element(game_state, mines_left, time, pixel_size, skin)
+}
+
+pub fn new_game(skin: &Skin) -> Element<'static, BoardMessage> {
+    new_game::new_game_element(skin)
+}
+
+pub fn custom_game<'a>(
+    form: &'a CustomGameForm,
+    presets: &'a Presets,
+    first_click: FirstClickPolicy,
+) -> Element<'a, BoardMessage> {
+    custom_game::custom_game_element(form, presets, first_click)
+}
+
+pub fn error_banner(error: &AppError) -> Element<'static, BoardMessage> {
+    error_banner::error_banner_element(error)
+}
+
+pub fn statistics(history: &History, filter: Option<Difficulty>) -> Element<'static, BoardMessage> {
+    statistics::statistics_element(history, filter)
+}
+
+pub fn multiplayer<'a>(
+    address: &'a str,
+    mode: Mode,
+    session: Option<&Session>,
+) -> Element<'a, BoardMessage> {
+    multiplayer::multiplayer_element(address, mode, session)
+}
+
+pub fn race_progress(
+    progress: &HashMap<usize, Progress>,
+    pixel_size: f32,
+) -> Element<'static, BoardMessage> {
+    race::race_progress_element(progress, pixel_size)
+}
+
+pub fn race_result(session: &Session, own: Progress) -> Element<'static, BoardMessage> {
+    race::race_result_element(session, own)
 }
 
-pub fn top_bar(game_state: &GameState, time: usize) -> Element<'_, BoardMessage> {
-    top_bar::top_bar_element(game_state, time)
+pub fn daily(history: &DailyHistory, today: u64, can_play: bool) -> Element<'static, BoardMessage> {
+    daily::daily_element(history, today, can_play)
 }
 
-pub fn new_game() -> Element<'static, BoardMessage> {
-    new_game::new_game_element()
+pub fn settings<'a>(
+    config: &'a Config,
+    form: &'a SettingsForm,
+    scale: u16,
+    skins: &[String],
+) -> Element<'a, BoardMessage> {
+    settings::settings_element(config, form, scale, skins)
 }
@@ -0,0 +1,71 @@
+use crate::messages::BoardMessage;
+use crate::network::{Mode, Role, Session};
+use iced::widget::{button, column, container, pick_list, row, text, text_input};
+use iced::{Element, Length};
+
+fn status(session: &Session) -> String {
+    match (session.role, session.is_connected()) {
+        (Role::Host, true) => format!(
+            "Hosting a {} game on {} with {} other player(s)",
+            session.mode,
+            session.address,
+            session.peers.len()
+        ),
+        (Role::Host, false) => format!("Starting to host on {}...", session.address),
+        (Role::Client, true) => format!(
+            "Connected to a {} game on {} as player {}",
+            session.mode,
+            session.address,
+            session.player + 1
+        ),
+        (Role::Client, false) => format!("Connecting to {}...", session.address),
+    }
+}
+
+pub fn multiplayer_element<'a>(
+    address: &'a str,
+    mode: Mode,
+    session: Option<&Session>,
+) -> Element<'a, BoardMessage> {
+    let controls = match session {
+        // The address can't change while connected
+        Some(session) => column![
+            text_input("Address", address),
+            text(status(session)),
+            button("Leave")
+                .style(button::danger)
+                .on_press(BoardMessage::LeaveGame),
+        ],
+        None => column![
+            text_input("Address", address).on_input(BoardMessage::MultiplayerAddressChanged),
+            text("Host a game others can join, or join a game on your network"),
+            row![
+                text("Mode when hosting"),
+                pick_list(Mode::ALL, Some(mode), BoardMessage::MultiplayerModeChanged),
+            ]
+            .spacing(8),
+            row![
+                button("Host").on_press(BoardMessage::HostGame),
+                button("Join").on_press(BoardMessage::JoinGame),
+            ]
+            .spacing(8),
+        ],
+    }
+    .spacing(8);
+
+    container(
+        column![
+            row![
+                text("Multiplayer").size(24).width(Length::Fill),
+                button("Back").on_press(BoardMessage::CloseScreen),
+            ],
+            controls,
+            text("The host starts new games. In co-op everyone plays on the same board, in a race everyone gets their own copy of it"),
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -1,14 +1,14 @@
-use crate::{messages::BoardMessage, resources};
+use crate::{messages::BoardMessage, resources::Skin};
 use iced::Length;
 use iced::widget::image::FilterMethod;
-use iced::widget::{column, container, image, mouse_area, row};
+use iced::widget::{button, column, container, image, mouse_area, row};
 
-pub fn new_game_element() -> iced::Element<'static, BoardMessage> {
+pub fn new_game_element(skin: &Skin) -> iced::Element<'static, BoardMessage> {
     column![
         row![
             container(
                 mouse_area(
-                    image(resources::get_image_handle("difficulty/beginner.png"))
+                    image(skin.get_image_handle("difficulty/beginner.png"))
                         .width(Length::Fill)
                         .height(Length::Fill)
                         .filter_method(FilterMethod::Nearest)
@@ -19,7 +19,7 @@ pub fn new_game_element() -> iced::Element<'static, BoardMessage> {
             .center_y(Length::Fill),
             container(
                 mouse_area(
-                    image(resources::get_image_handle("difficulty/intermediate.png"))
+                    image(skin.get_image_handle("difficulty/intermediate.png"))
                         .width(Length::Fill)
                         .height(Length::Fill)
                         .filter_method(FilterMethod::Nearest)
@@ -32,7 +32,7 @@ pub fn new_game_element() -> iced::Element<'static, BoardMessage> {
         row![
             container(
                 mouse_area(
-                    image(resources::get_image_handle("difficulty/expert.png"))
+                    image(skin.get_image_handle("difficulty/expert.png"))
                         .width(Length::Fill)
                         .height(Length::Fill)
                         .filter_method(FilterMethod::Nearest)
@@ -43,13 +43,27 @@ pub fn new_game_element() -> iced::Element<'static, BoardMessage> {
             .center_y(Length::Fill),
             container(
                 mouse_area(
-                    image(resources::get_image_handle("difficulty/null.png"))
+                    image(skin.get_image_handle("difficulty/null.png"))
                         .width(Length::Fill)
                         .height(Length::Fill)
                         .filter_method(FilterMethod::Nearest)
-                ) // .on_release(BoardMessage::SubmitNewGame(30, 16, 99))
+                )
+                .on_release(BoardMessage::OpenCustomGame)
             )
+            .center_x(Length::Fill)
+            .center_y(Length::Fill),
         ],
+        container(
+            row![
+                button("Daily").on_press(BoardMessage::OpenDaily),
+                button("Statistics").on_press(BoardMessage::OpenStatistics),
+                button("Multiplayer").on_press(BoardMessage::OpenMultiplayer),
+                button("Settings").on_press(BoardMessage::OpenSettings),
+            ]
+            .spacing(8)
+        )
+        .center_x(Length::Fill)
+        .padding(8),
     ]
     .width(Length::Fill)
     .height(Length::Fill)
@@ -0,0 +1,36 @@
+use crate::globals;
+use crate::messages::BoardMessage;
+use iced::widget::{center, column, mouse_area, text};
+use iced::{Element, Theme, widget::container};
+
+fn paused_style(theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(theme.extended_palette().background.weak.color.into()),
+        ..container::Style::default()
+    }
+}
+
+// Takes the place of the grid, with the same size so the window doesn't jump around
+pub fn paused_element(
+    size_x: usize,
+    size_y: usize,
+    pixel_size: f32,
+) -> Element<'static, BoardMessage> {
+    let cell_size = globals::cell_size(pixel_size);
+
+    mouse_area(
+        center(
+            column![
+                text("Paused").size(24),
+                text("Click or press any key to resume"),
+            ]
+            .spacing(8)
+            .align_x(iced::Alignment::Center),
+        )
+        .width(cell_size * size_x as f32)
+        .height(cell_size * size_y as f32)
+        .style(paused_style),
+    )
+    .on_press(BoardMessage::Resume)
+    .into()
+}
@@ -0,0 +1,112 @@
+use crate::globals;
+use crate::messages::BoardMessage;
+use crate::network::{Progress, Role, Session};
+use crate::stats;
+use iced::widget::{Column, Row, button, column, container, progress_bar, row, text};
+use iced::{Element, Length};
+use minesweeper_engine::GameState;
+use std::collections::HashMap;
+
+fn player_name(player: usize) -> String {
+    format!("Player {}", player + 1)
+}
+
+fn outcome(progress: &Progress) -> String {
+    match progress.state {
+        GameState::Won => format!("cleared in {}", stats::format_time(progress.time_ms)),
+        GameState::Lost => format!("hit a mine at {:.0}%", progress.cleared * 100.0),
+        GameState::Playing => format!("playing, {:.0}% cleared", progress.cleared * 100.0),
+    }
+}
+
+// Opponents' share of cleared safe cells, shown under the board during a race
+pub fn race_progress_element(
+    progress: &HashMap<usize, Progress>,
+    pixel_size: f32,
+) -> Element<'static, BoardMessage> {
+    let mut players = progress.iter().collect::<Vec<_>>();
+    players.sort_by_key(|(player, _)| **player);
+
+    let bars = players.into_iter().map(|(player, progress)| {
+        row![
+            text(player_name(*player)).size(12),
+            progress_bar(0.0..=1.0, progress.cleared).height(8),
+        ]
+        .spacing(4)
+        .width(Length::Fill)
+        .into()
+    });
+
+    container(Row::with_children(bars).spacing(8))
+        .padding(4)
+        .width(Length::Fill)
+        .height(globals::cell_size(pixel_size))
+        .center_y(globals::cell_size(pixel_size))
+        .into()
+}
+
+pub fn race_result_element(session: &Session, own: Progress) -> Element<'static, BoardMessage> {
+    let mut standings = session
+        .progress
+        .iter()
+        .map(|(player, progress)| (Some(*player), *progress))
+        .chain(std::iter::once((None, own)))
+        .collect::<Vec<_>>();
+
+    // Winners by time first, then everyone else by how much they cleared
+    standings.sort_by(|(_, a), (_, b)| match (a.state, b.state) {
+        (GameState::Won, GameState::Won) => a.time_ms.cmp(&b.time_ms),
+        (GameState::Won, _) => std::cmp::Ordering::Less,
+        (_, GameState::Won) => std::cmp::Ordering::Greater,
+        _ => b.cleared.total_cmp(&a.cleared),
+    });
+
+    let name = |player: Option<usize>| player.map_or("You".to_string(), player_name);
+
+    let still_playing = standings
+        .iter()
+        .filter(|(_, progress)| progress.state == GameState::Playing)
+        .count();
+
+    // A racer that is still playing could still beat the fastest time so far
+    let headline = match standings.first() {
+        _ if still_playing > 0 => format!("Waiting for {} player(s) to finish", still_playing),
+        Some((None, progress)) if progress.state == GameState::Won => "You win!".to_string(),
+        Some((player, progress)) if progress.state == GameState::Won => {
+            format!("{} wins", name(*player))
+        }
+        _ => "Nobody cleared the board".to_string(),
+    };
+
+    let rows = standings
+        .iter()
+        .enumerate()
+        .map(|(place, (player, progress))| {
+            text(format!(
+                "{}. {}: {}",
+                place + 1,
+                name(*player),
+                outcome(progress)
+            ))
+            .into()
+        });
+
+    let mut buttons = row![button("Board").on_press(BoardMessage::CloseScreen)].spacing(8);
+    if session.role == Role::Host {
+        buttons = buttons.push(button("New race").on_press(BoardMessage::OpenNewGameModal));
+    }
+
+    container(
+        column![
+            text("Race results").size(24),
+            text(headline).size(20),
+            Column::with_children(rows).spacing(4),
+            buttons,
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -0,0 +1,142 @@
+use crate::config::Config;
+use crate::globals;
+use crate::input::{self, Command, KeyBindings, MouseAction};
+use crate::messages::BoardMessage;
+use crate::themes;
+use iced::mouse::Button;
+use iced::widget::{
+    Column, button, checkbox, column, container, pick_list, row, scrollable, text, text_input,
+};
+use iced::{Alignment, Element, Length};
+use minesweeper_engine::FirstClickPolicy;
+
+// The key bindings as typed, one comma separated list per command in `Command::ALL` order.
+// Kept apart from the config so a half typed list like "w, " isn't cleaned up while typing
+#[derive(Debug, Clone)]
+pub struct SettingsForm {
+    pub keys: Vec<String>,
+}
+
+impl SettingsForm {
+    pub fn new(bindings: &KeyBindings) -> Self {
+        Self {
+            keys: Command::ALL
+                .iter()
+                .map(|command| bindings.keys(*command).join(", "))
+                .collect(),
+        }
+    }
+}
+
+// Splits a comma separated list of key names, dropping empty entries
+pub fn parse_keys(keys: &str) -> Vec<String> {
+    keys.split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn labelled<'a>(
+    label: impl text::IntoFragment<'a>,
+    control: impl Into<Element<'a, BoardMessage>>,
+) -> Element<'a, BoardMessage> {
+    row![text(label).width(120), control.into()]
+        .spacing(8)
+        .into()
+}
+
+fn mouse_row(button: Button, action: MouseAction) -> Element<'static, BoardMessage> {
+    labelled(
+        input::button_name(button),
+        pick_list(MouseAction::ALL, Some(action), move |action| {
+            BoardMessage::MouseBindingChanged(button, action)
+        }),
+    )
+}
+
+pub fn settings_element<'a>(
+    config: &'a Config,
+    form: &'a SettingsForm,
+    scale: u16,
+    skins: &[String],
+) -> Element<'a, BoardMessage> {
+    let mouse = config
+        .bindings
+        .mouse
+        .buttons()
+        .into_iter()
+        .map(|(button, action)| mouse_row(button, action));
+
+    let keys = Command::ALL.iter().zip(&form.keys).map(|(command, keys)| {
+        let command = *command;
+        labelled(
+            command.name(),
+            text_input("", keys)
+                .on_input(move |keys| BoardMessage::KeyBindingChanged(command, keys))
+                .width(Length::Fill),
+        )
+    });
+
+    container(scrollable(
+        column![
+            row![
+                text("Settings").size(24).width(Length::Fill),
+                button("Back").on_press(BoardMessage::OpenNewGameModal),
+            ],
+            text("Game").size(20),
+            labelled(
+                "First click",
+                pick_list(
+                    FirstClickPolicy::ALL,
+                    Some(config.first_click),
+                    BoardMessage::FirstClickChanged
+                ),
+            ),
+            checkbox("Question marks", config.question_marks)
+                .on_toggle(BoardMessage::QuestionMarksToggled),
+            text("Appearance").size(20),
+            labelled(
+                "Scale",
+                row![
+                    pick_list(
+                        (globals::MIN_SCALE..=globals::MAX_SCALE).collect::<Vec<_>>(),
+                        Some(scale),
+                        BoardMessage::ScaleChanged
+                    ),
+                    checkbox("Automatic", config.scale.is_none())
+                        .on_toggle(BoardMessage::AutoScaleToggled),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            ),
+            text("Ctrl with scroll, + or - zooms, Ctrl+0 goes back to automatic").size(12),
+            labelled(
+                "Theme",
+                pick_list(
+                    themes::all(),
+                    Some(config.theme()),
+                    BoardMessage::ThemeChanged
+                ),
+            ),
+            labelled(
+                "Skin",
+                pick_list(
+                    skins.to_vec(),
+                    Some(config.skin.clone()),
+                    BoardMessage::SkinChanged
+                ),
+            ),
+            text("Mouse").size(20),
+            Column::with_children(mouse).spacing(12),
+            text("Keys").size(20),
+            text("Comma separated, like \"w, ArrowUp\"").size(12),
+            Column::with_children(keys).spacing(4),
+        ]
+        .spacing(12)
+        .padding(16),
+    ))
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
@@ -0,0 +1,144 @@
+use crate::messages::BoardMessage;
+use crate::resources::Skin;
+use iced::widget::image::FilterMethod;
+use iced::widget::{Row, Theme, container, image};
+use iced::{Border, Color, Element, Length};
+
+// Size of the `red_text` glyphs in sprite pixels
+const GLYPH_WIDTH: f32 = 16.0;
+const GLYPH_HEIGHT: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    // Stick to the widest value that fits, like 999 for three digits
+    Clamp,
+    // Add digits as needed
+    Expand,
+}
+
+// A row of seven segment digits, like the classic mine counter and timer
+#[derive(Debug, Clone, Copy)]
+pub struct SevenSegment {
+    digits: usize,
+    overflow: Overflow,
+    // Screen pixels per sprite pixel
+    scale: f32,
+}
+
+impl SevenSegment {
+    pub fn new(digits: usize) -> Self {
+        Self {
+            digits: digits.max(1),
+            overflow: Overflow::Clamp,
+            scale: 1.0,
+        }
+    }
+
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    // The characters shown for `number`, padded with zeros. A negative number spends one
+    // digit on the minus sign
+    pub fn format(&self, number: isize) -> String {
+        let number = match self.overflow {
+            Overflow::Expand => number,
+            Overflow::Clamp => {
+                let max = 10isize.saturating_pow(self.digits as u32) - 1;
+                let min = -(10isize.saturating_pow(self.digits as u32 - 1) - 1);
+                number.clamp(min, max)
+            }
+        };
+
+        match number {
+            ..0 => format!(
+                "-{:0width$}",
+                number.unsigned_abs(),
+                width = self.digits - 1
+            ),
+            _ => format!("{:0width$}", number, width = self.digits),
+        }
+    }
+
+    pub fn view(self, number: isize, skin: &Skin) -> Element<'static, BoardMessage> {
+        let glyphs = self
+            .format(number)
+            .chars()
+            .map(|c| {
+                image(skin.get_image_handle(&format!("red_text/{}.png", c)))
+                    .filter_method(FilterMethod::Nearest)
+                    .width(GLYPH_WIDTH * self.scale)
+                    .height(GLYPH_HEIGHT * self.scale)
+                    .into()
+            })
+            .collect::<Vec<_>>();
+
+        container(Row::from_vec(glyphs).spacing(self.scale * 2.0))
+            .padding(self.scale * 2.0)
+            .height(Length::Shrink)
+            .width(Length::Shrink)
+            .style(move |theme| style(theme, self.scale))
+            .into()
+    }
+}
+
+// The red digits need a dark background whatever the theme, so it is the theme's
+// background darkened most of the way to black
+fn style(theme: &Theme, scale: f32) -> container::Style {
+    let palette = theme.extended_palette();
+    let background = palette.background.base.color;
+
+    container::Style {
+        background: Some(
+            Color::from_rgb(
+                background.r * 0.15,
+                background.g * 0.15,
+                background.b * 0.15,
+            )
+            .into(),
+        ),
+        border: Border::default()
+            .color(palette.background.strong.color)
+            .width(scale * 0.5),
+        ..container::Style::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_sticks_to_what_fits() {
+        let counter = SevenSegment::new(3);
+
+        assert_eq!(counter.format(5), "005");
+        assert_eq!(counter.format(999), "999");
+        assert_eq!(counter.format(1234), "999");
+        assert_eq!(counter.format(-5), "-05");
+        assert_eq!(counter.format(-150), "-99");
+    }
+
+    #[test]
+    fn expand_adds_digits() {
+        let counter = SevenSegment::new(3).overflow(Overflow::Expand);
+
+        assert_eq!(counter.format(42), "042");
+        assert_eq!(counter.format(1234), "1234");
+        assert_eq!(counter.format(-150), "-150");
+    }
+
+    #[test]
+    fn one_digit_has_no_room_for_a_minus() {
+        let counter = SevenSegment::new(0);
+
+        assert_eq!(counter.format(12), "9");
+        assert_eq!(counter.format(-3), "0");
+    }
+}
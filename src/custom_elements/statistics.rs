@@ -0,0 +1,121 @@
+use crate::messages::BoardMessage;
+use crate::stats::{self, History, Statistics};
+use iced::widget::{Column, Row, button, column, container, row, scrollable, text};
+use iced::{Element, Length, Theme};
+use minesweeper_engine::Difficulty;
+
+const BAR_WIDTH: f32 = 200.0;
+
+fn difficulty_label(difficulty: &Difficulty) -> String {
+    match difficulty.name() {
+        "Custom" => format!(
+            "{}x{}/{}",
+            difficulty.size_x, difficulty.size_y, difficulty.bomb_count
+        ),
+        name => name.to_string(),
+    }
+}
+
+fn optional_time(time_ms: Option<u64>) -> String {
+    time_ms.map_or("-".to_string(), stats::format_time)
+}
+
+fn bar_style(theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(theme.extended_palette().primary.strong.color.into()),
+        ..container::Style::default()
+    }
+}
+
+fn histogram(statistics: &Statistics) -> Element<'static, BoardMessage> {
+    let Some(max) = statistics.histogram.iter().map(|b| b.count).max() else {
+        return text("No games won yet").into();
+    };
+
+    let bars = statistics.histogram.iter().map(|bin| {
+        row![
+            text(format!("{}-{}s", bin.from_ms / 1000, bin.to_ms / 1000)).width(80),
+            container("")
+                .width(BAR_WIDTH * bin.count as f32 / max as f32)
+                .height(16)
+                .style(bar_style),
+            text(bin.count),
+        ]
+        .spacing(8)
+        .into()
+    });
+
+    Column::with_children(bars).spacing(4).into()
+}
+
+pub fn statistics_element(
+    history: &History,
+    filter: Option<Difficulty>,
+) -> Element<'static, BoardMessage> {
+    let mut difficulties = vec![
+        Difficulty::BEGINNER,
+        Difficulty::INTERMEDIATE,
+        Difficulty::EXPERT,
+    ];
+    for record in &history.records {
+        if !difficulties.contains(&record.difficulty()) {
+            difficulties.push(record.difficulty());
+        }
+    }
+
+    let tabs = std::iter::once((None, "All".to_string()))
+        .chain(difficulties.iter().map(|d| (Some(*d), difficulty_label(d))))
+        .map(|(difficulty, label)| {
+            let tab = button(text(label));
+            match difficulty == filter {
+                true => tab,
+                false => tab
+                    .style(button::secondary)
+                    .on_press(BoardMessage::SelectStatistics(difficulty)),
+            }
+            .into()
+        });
+
+    let statistics = history.statistics(filter);
+
+    let summary = column![
+        text(format!("Games played: {}", statistics.played)),
+        text(format!("Games won: {}", statistics.won)),
+        text(format!("Win rate: {:.1}%", statistics.win_rate())),
+        text(format!("Current streak: {}", statistics.current_streak)),
+        text(format!("Best streak: {}", statistics.best_streak)),
+        text(format!(
+            "Best time: {}",
+            optional_time(statistics.best_time_ms)
+        )),
+        text(format!(
+            "Average time: {}",
+            optional_time(statistics.average_time_ms)
+        )),
+        text(format!(
+            "Median time: {}",
+            optional_time(statistics.median_time_ms)
+        )),
+    ]
+    .spacing(4);
+
+    container(
+        column![
+            row![
+                text("Statistics").size(24).width(Length::Fill),
+                button("Back").on_press(BoardMessage::CloseScreen),
+            ],
+            scrollable(Row::with_children(tabs).spacing(4)).direction(
+                scrollable::Direction::Horizontal(scrollable::Scrollbar::default())
+            ),
+            summary,
+            text("Completion times").size(20),
+            histogram(&statistics),
+        ]
+        .spacing(12),
+    )
+    .padding(16)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
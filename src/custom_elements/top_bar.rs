@@ -1,77 +1,65 @@
+use super::seven_segment::{Overflow, SevenSegment};
 use crate::globals;
 use crate::messages::BoardMessage;
-use crate::minesweeper::GameState;
-use crate::resources;
+use crate::resources::Skin;
+use minesweeper_engine::GameState;
 
 use iced::widget::image::FilterMethod;
-use iced::widget::{Row, Theme, container, image, mouse_area, row};
-use iced::{Border, Element, Length, border, color};
+use iced::widget::{Theme, container, image, mouse_area, row};
+use iced::{Element, Length, border};
 
-fn red_text(number: usize) -> Element<'static, BoardMessage> {
-    let n = number + 1000;
-
-    let row = n.to_string()[1..4]
-        .chars()
-        .map(|i| {
-            image(resources::get_image_handle(format!("red_text/{}.png", i)))
-                .filter_method(FilterMethod::Nearest)
-                .width(globals::SCALE)
-                .height(globals::SCALE as f32 * 1.5)
-                .into()
-        })
-        .collect::<Vec<_>>();
-
-    container(Row::from_vec(row).spacing(globals::PIXEL_SIZE * 2.0))
-        .padding(globals::PIXEL_SIZE * 2.0)
-        .height(Length::Shrink)
-        .width(Length::Shrink)
-        .style(red_text_style)
-        .into()
-}
-
-fn red_text_style(_theme: &Theme) -> container::Style {
-    container::Style {
-        background: Some(color!(0x0b0b1c).into()),
-        border: Border::default()
-            .color(color!(0x3d3d3d))
-            .width(globals::PIXEL_SIZE * 0.5),
-        ..container::Style::default()
-    }
-}
-
-fn top_bar_style(_theme: &Theme) -> container::Style {
+fn top_bar_style(theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(color!(0x828282).into()),
+        background: Some(theme.extended_palette().background.strong.color.into()),
         border: border::rounded(0),
         ..container::Style::default()
     }
 }
 
-pub fn top_bar_element(game_state: &GameState, time: usize) -> Element<'_, BoardMessage> {
-    let image_name = format!(
-        "{}.png",
-        match game_state {
-            GameState::Playing => "playing",
-            GameState::Lost => "lost",
-            GameState::Won => "won",
-        }
-    );
+pub fn top_bar_element(
+    game_state: GameState,
+    mines_left: isize,
+    time: usize,
+    pixel_size: f32,
+    skin: &Skin,
+) -> Element<'static, BoardMessage> {
+    let cell_size = globals::cell_size(pixel_size);
+    // Two thirds of a cell wide per digit so both counters and the face fit above the
+    // eight cell wide beginner board
+    let digit_scale = pixel_size * 2.0 / 3.0;
+
+    let face = match game_state {
+        GameState::Playing => "playing.png",
+        GameState::Lost => "lost.png",
+        GameState::Won => "won.png",
+    };
 
     row![
+        // Remaining mines
+        // Custom boards can have more than 999 mines, so this one grows instead of clamping
+        container(
+            SevenSegment::new(3)
+                .overflow(Overflow::Expand)
+                .scale(digit_scale)
+                .view(mines_left, skin)
+        )
+        .center_y(Length::Fill)
+        .center_x(Length::Fill)
+        .style(top_bar_style),
         // Centre
         container(
             // Face
             container(
                 mouse_area(
-                    image(resources::get_image_handle(image_name))
+                    image(skin.get_image_handle(face))
                         .filter_method(FilterMethod::Nearest)
-                        .height(globals::SCALE)
-                        .width(globals::SCALE)
+                        .height(cell_size)
+                        .width(cell_size)
                 )
                 .on_release(BoardMessage::OpenNewGameModal)
             )
-            .height(globals::SCALE)
-            .width(globals::SCALE)
+            .height(cell_size)
+            .width(cell_size)
         )
         .style(top_bar_style)
         .width(Length::Fill)
@@ -79,12 +67,16 @@ pub fn top_bar_element(game_state: &GameState, time: usize) -> Element<'_, Board
         .center_x(Length::Fill)
         .center_y(Length::Fill),
         // Timer
-        container(red_text(time))
-            .center_y(Length::Fill)
-            .center_x(Length::Fill)
-            .style(top_bar_style),
+        container(
+            SevenSegment::new(3)
+                .scale(digit_scale)
+                .view(time as isize, skin)
+        )
+        .center_y(Length::Fill)
+        .center_x(Length::Fill)
+        .style(top_bar_style),
     ]
     .width(Length::Fill)
-    .height(globals::SCALE * 2)
+    .height(cell_size * 2.0)
     .into()
 }
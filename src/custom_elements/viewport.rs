@@ -0,0 +1,21 @@
+use crate::messages::BoardMessage;
+use iced::widget::scrollable::{self, Direction, Scrollable, Scrollbar};
+use iced::{Element, Length};
+
+pub fn id() -> scrollable::Id {
+    scrollable::Id::new("board-viewport")
+}
+
+// Boards too big for the window scroll inside it, under the top bar
+pub fn viewport_element<'a>(board: Element<'a, BoardMessage>) -> Element<'a, BoardMessage> {
+    Scrollable::new(board)
+        .id(id())
+        .direction(Direction::Both {
+            vertical: Scrollbar::default(),
+            horizontal: Scrollbar::default(),
+        })
+        .on_scroll(BoardMessage::BoardScrolled)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
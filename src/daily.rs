@@ -0,0 +1,243 @@
+use crate::error::AppError;
+use crate::storage;
+use minesweeper_engine::{BoardOptions, Difficulty, FirstClickPolicy};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const DAILY_FILE: &str = "daily.json";
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// The daily difficulty cycles through these, one per day
+const DIFFICULTIES: [Difficulty; 3] = [
+    Difficulty::BEGINNER,
+    Difficulty::INTERMEDIATE,
+    Difficulty::EXPERT,
+];
+
+// Days since the unix epoch in UTC, so everyone gets the same board whatever their timezone
+pub fn today() -> u64 {
+    storage::now() / SECONDS_PER_DAY
+}
+
+// Turns a day number into YYYY-MM-DD, see http://howardhinnant.github.io/date_algorithms.html
+pub fn format_day(day: u64) -> String {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Challenge {
+    pub day: u64,
+    pub difficulty: Difficulty,
+    pub seed: u64,
+}
+
+impl Challenge {
+    pub fn of(day: u64) -> Self {
+        // splitmix64, so the seeds of neighbouring days have nothing in common
+        let mut seed = day.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        seed ^= seed >> 31;
+
+        Self {
+            day,
+            difficulty: DIFFICULTIES[(day % DIFFICULTIES.len() as u64) as usize],
+            seed,
+        }
+    }
+
+    // The first click policy changes where mines end up, so it is fixed for everyone.
+    // Question marks don't change the board and are left to the player
+    pub fn options(&self, question_marks: bool) -> BoardOptions {
+        BoardOptions {
+            first_click: FirstClickPolicy::Safe,
+            seed: Some(self.seed),
+            question_marks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub day: u64,
+    pub won: bool,
+    // None until the game is finished, an abandoned attempt stays a loss
+    pub time_ms: Option<u64>,
+}
+
+// One result per day the challenge was attempted, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct DailyHistory {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn load() -> Result<Self, AppError> {
+        let Some(path) = storage::data_path(DAILY_FILE) else {
+            return Ok(Self::default());
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+
+        let results = serde_json::from_str(&contents).map_err(|e| storage::set_aside(&path, e))?;
+
+        Ok(Self { results })
+    }
+
+    fn save(&self) -> Result<(), AppError> {
+        self.write()
+            .map_err(|e| AppError::Save(format!("{}: {}", DAILY_FILE, e)))
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        let Some(path) = storage::data_path(DAILY_FILE) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(&self.results)?)
+    }
+
+    pub fn result(&self, day: u64) -> Option<&DailyResult> {
+        self.results.iter().find(|result| result.day == day)
+    }
+
+    // The attempt is stored as soon as it starts, so quitting can't be used for a retry
+    pub fn start(&mut self, day: u64) -> Result<(), AppError> {
+        if self.result(day).is_none() {
+            self.results.push(DailyResult {
+                day,
+                won: false,
+                time_ms: None,
+            });
+        }
+        self.save()
+    }
+
+    pub fn finish(&mut self, day: u64, won: bool, time_ms: u64) -> Result<(), AppError> {
+        if let Some(result) = self.results.iter_mut().find(|result| result.day == day) {
+            result.won = won;
+            result.time_ms = Some(time_ms);
+        }
+        self.save()
+    }
+
+    fn won(&self, day: u64) -> bool {
+        self.result(day).is_some_and(|result| result.won)
+    }
+
+    // Days won in a row up to today. Today not being played yet doesn't break the streak
+    pub fn current_streak(&self, today: u64) -> usize {
+        let last = match self.won(today) || today == 0 {
+            true => today,
+            false => today - 1,
+        };
+
+        (0..=last).rev().take_while(|day| self.won(*day)).count()
+    }
+
+    pub fn best_streak(&self) -> usize {
+        let mut days = self
+            .results
+            .iter()
+            .filter(|result| result.won)
+            .map(|result| result.day)
+            .collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut best = 0;
+        let mut current = 0;
+        for (i, day) in days.iter().enumerate() {
+            current = match i > 0 && days[i - 1] + 1 == *day {
+                true => current + 1,
+                false => 1,
+            };
+            best = best.max(current);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(results: &[(u64, bool)]) -> DailyHistory {
+        DailyHistory {
+            results: results
+                .iter()
+                .map(|&(day, won)| DailyResult {
+                    day,
+                    won,
+                    time_ms: Some(1000),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn current_streak_waits_for_today() {
+        let daily = history(&[(7, true), (8, true), (9, true)]);
+
+        assert_eq!(daily.current_streak(9), 3);
+        assert_eq!(daily.current_streak(10), 3);
+        assert_eq!(daily.current_streak(11), 0);
+    }
+
+    #[test]
+    fn a_loss_ends_the_streak() {
+        let daily = history(&[(8, true), (9, false)]);
+        assert_eq!(daily.current_streak(10), 0);
+
+        let daily = history(&[(8, true), (9, false), (10, true)]);
+        assert_eq!(daily.current_streak(10), 1);
+        assert_eq!(history(&[(0, true)]).current_streak(0), 1);
+    }
+
+    #[test]
+    fn best_streak_is_the_longest_run() {
+        let daily = history(&[
+            (3, true),
+            (1, true),
+            (2, true),
+            (2, true),
+            (5, true),
+            (6, false),
+            (7, true),
+        ]);
+
+        assert_eq!(daily.best_streak(), 3);
+        assert_eq!(DailyHistory::default().best_streak(), 0);
+    }
+
+    #[test]
+    fn challenges_are_the_same_for_everyone() {
+        assert_eq!(Challenge::of(19_723), Challenge::of(19_723));
+        assert_ne!(Challenge::of(19_723).seed, Challenge::of(19_724).seed);
+        assert_eq!(Challenge::of(0).difficulty, Difficulty::BEGINNER);
+        assert_eq!(Challenge::of(2).difficulty, Difficulty::EXPERT);
+    }
+
+    #[test]
+    fn days_format_as_dates() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(19_723), "2024-01-01");
+        assert_eq!(format_day(19_782), "2024-02-29");
+    }
+}
@@ -0,0 +1,30 @@
+use minesweeper_engine::BoardError;
+use std::fmt;
+
+// Everything that ends up in the error banner
+#[derive(Debug, Clone)]
+pub enum AppError {
+    Board(BoardError),
+    Network(String),
+    // A settings, skin, history or other app file that couldn't be read
+    InvalidFile(String),
+    // Writing one of them failed
+    Save(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Board(e) => write!(f, "{}", e),
+            AppError::Network(e) => write!(f, "{}", e),
+            AppError::InvalidFile(reason) => write!(f, "Invalid file: {}", reason),
+            AppError::Save(reason) => write!(f, "Could not save {}", reason),
+        }
+    }
+}
+
+impl From<BoardError> for AppError {
+    fn from(e: BoardError) -> Self {
+        AppError::Board(e)
+    }
+}
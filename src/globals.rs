@@ -1,3 +1,20 @@
 // Iced doesn't let you use u32 as scale
 pub const SCALE: u16 = 48;
-pub const PIXEL_SIZE: f32 = (SCALE / 16) as f32;
+
+// Cells and the face are 16x16 sprites
+pub const SPRITE_SIZE: f32 = 16.0;
+
+// Zoom levels count physical screen pixels per sprite pixel, so sprites stay sharp
+// whatever the monitor's scale factor
+pub const DEFAULT_SCALE: u16 = 3;
+pub const MIN_SCALE: u16 = 1;
+pub const MAX_SCALE: u16 = 12;
+
+// Iced lays widgets out in logical pixels, which are `scale_factor` physical pixels wide
+pub fn pixel_size(scale: u16, scale_factor: f32) -> f32 {
+    scale as f32 / scale_factor
+}
+
+pub fn cell_size(pixel_size: f32) -> f32 {
+    SPRITE_SIZE * pixel_size
+}
@@ -0,0 +1,324 @@
+use iced::keyboard::Key;
+use iced::mouse;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Up,
+    Down,
+    Left,
+    Right,
+    Reveal,
+    Flag,
+    Chord,
+    NewGame,
+    Restart,
+    Pause,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+}
+
+impl Command {
+    pub const ALL: [Command; 14] = [
+        Command::Up,
+        Command::Down,
+        Command::Left,
+        Command::Right,
+        Command::Reveal,
+        Command::Flag,
+        Command::Chord,
+        Command::NewGame,
+        Command::Restart,
+        Command::Pause,
+        Command::ScrollUp,
+        Command::ScrollDown,
+        Command::ScrollLeft,
+        Command::ScrollRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Up => "Up",
+            Command::Down => "Down",
+            Command::Left => "Left",
+            Command::Right => "Right",
+            Command::Reveal => "Reveal",
+            Command::Flag => "Flag",
+            Command::Chord => "Chord",
+            Command::NewGame => "New game",
+            Command::Restart => "Restart",
+            Command::Pause => "Pause",
+            Command::ScrollUp => "Scroll up",
+            Command::ScrollDown => "Scroll down",
+            Command::ScrollLeft => "Scroll left",
+            Command::ScrollRight => "Scroll right",
+        }
+    }
+}
+
+// What a mouse button does on a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MouseAction {
+    Reveal,
+    Flag,
+    Chord,
+    None,
+}
+
+impl MouseAction {
+    pub const ALL: [MouseAction; 4] = [
+        MouseAction::Reveal,
+        MouseAction::Flag,
+        MouseAction::Chord,
+        MouseAction::None,
+    ];
+}
+
+impl fmt::Display for MouseAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MouseAction::Reveal => "Reveal",
+            MouseAction::Flag => "Flag",
+            MouseAction::Chord => "Chord",
+            MouseAction::None => "Nothing",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MouseBindings {
+    pub left: MouseAction,
+    pub right: MouseAction,
+    pub middle: MouseAction,
+    // The side buttons many mice have
+    pub back: MouseAction,
+    pub forward: MouseAction,
+    // Any further buttons by the number the system gives them. TOML keys are strings, so
+    // the numbers are too
+    pub other: BTreeMap<String, MouseAction>,
+}
+
+impl Default for MouseBindings {
+    fn default() -> Self {
+        Self {
+            left: MouseAction::Reveal,
+            right: MouseAction::Flag,
+            middle: MouseAction::Chord,
+            back: MouseAction::None,
+            forward: MouseAction::None,
+            other: BTreeMap::new(),
+        }
+    }
+}
+
+pub fn button_name(button: mouse::Button) -> String {
+    match button {
+        mouse::Button::Left => "Left button".to_string(),
+        mouse::Button::Right => "Right button".to_string(),
+        mouse::Button::Middle => "Middle button".to_string(),
+        mouse::Button::Back => "Back button".to_string(),
+        mouse::Button::Forward => "Forward button".to_string(),
+        mouse::Button::Other(number) => format!("Button {}", number),
+    }
+}
+
+impl MouseBindings {
+    pub fn action(&self, button: mouse::Button) -> MouseAction {
+        match button {
+            mouse::Button::Left => self.left,
+            mouse::Button::Right => self.right,
+            mouse::Button::Middle => self.middle,
+            mouse::Button::Back => self.back,
+            mouse::Button::Forward => self.forward,
+            mouse::Button::Other(number) => self
+                .other
+                .get(&number.to_string())
+                .copied()
+                .unwrap_or(MouseAction::None),
+        }
+    }
+
+    pub fn set(&mut self, button: mouse::Button, action: MouseAction) {
+        match button {
+            mouse::Button::Left => self.left = action,
+            mouse::Button::Right => self.right = action,
+            mouse::Button::Middle => self.middle = action,
+            mouse::Button::Back => self.back = action,
+            mouse::Button::Forward => self.forward = action,
+            mouse::Button::Other(number) => {
+                self.other.insert(number.to_string(), action);
+            }
+        }
+    }
+
+    // Every button with a binding, the named ones first
+    pub fn buttons(&self) -> Vec<(mouse::Button, MouseAction)> {
+        let named = [
+            mouse::Button::Left,
+            mouse::Button::Right,
+            mouse::Button::Middle,
+            mouse::Button::Back,
+            mouse::Button::Forward,
+        ];
+        let other = self
+            .other
+            .keys()
+            .filter_map(|number| number.parse().ok())
+            .map(mouse::Button::Other);
+
+        named
+            .into_iter()
+            .chain(other)
+            .map(|button| (button, self.action(button)))
+            .collect()
+    }
+}
+
+// Keys are written as the character they type (`"w"`) or the name of a special key
+// (`"ArrowUp"`, `"Space"`, `"Enter"`), ignoring case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub reveal: Vec<String>,
+    pub flag: Vec<String>,
+    pub chord: Vec<String>,
+    pub new_game: Vec<String>,
+    pub restart: Vec<String>,
+    pub pause: Vec<String>,
+    // Scroll boards bigger than the window by a page
+    pub scroll_up: Vec<String>,
+    pub scroll_down: Vec<String>,
+    pub scroll_left: Vec<String>,
+    pub scroll_right: Vec<String>,
+}
+
+fn keys(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: keys(&["ArrowUp", "w"]),
+            down: keys(&["ArrowDown", "s"]),
+            left: keys(&["ArrowLeft", "a"]),
+            right: keys(&["ArrowRight", "d"]),
+            reveal: keys(&["Space", "Enter"]),
+            flag: keys(&["f"]),
+            chord: keys(&["c"]),
+            new_game: keys(&["n"]),
+            restart: keys(&["r"]),
+            pause: keys(&["p", "Pause"]),
+            scroll_up: keys(&["PageUp"]),
+            scroll_down: keys(&["PageDown"]),
+            scroll_left: keys(&["Home"]),
+            scroll_right: keys(&["End"]),
+        }
+    }
+}
+
+fn key_name(key: &Key) -> Option<String> {
+    match key.as_ref() {
+        Key::Character(c) => Some(c.to_string()),
+        // The variant names are the names used in the config file
+        Key::Named(named) => Some(format!("{:?}", named)),
+        Key::Unidentified => None,
+    }
+}
+
+impl KeyBindings {
+    pub fn keys(&self, command: Command) -> &[String] {
+        match command {
+            Command::Up => &self.up,
+            Command::Down => &self.down,
+            Command::Left => &self.left,
+            Command::Right => &self.right,
+            Command::Reveal => &self.reveal,
+            Command::Flag => &self.flag,
+            Command::Chord => &self.chord,
+            Command::NewGame => &self.new_game,
+            Command::Restart => &self.restart,
+            Command::Pause => &self.pause,
+            Command::ScrollUp => &self.scroll_up,
+            Command::ScrollDown => &self.scroll_down,
+            Command::ScrollLeft => &self.scroll_left,
+            Command::ScrollRight => &self.scroll_right,
+        }
+    }
+
+    pub fn set(&mut self, command: Command, keys: Vec<String>) {
+        let bound = match command {
+            Command::Up => &mut self.up,
+            Command::Down => &mut self.down,
+            Command::Left => &mut self.left,
+            Command::Right => &mut self.right,
+            Command::Reveal => &mut self.reveal,
+            Command::Flag => &mut self.flag,
+            Command::Chord => &mut self.chord,
+            Command::NewGame => &mut self.new_game,
+            Command::Restart => &mut self.restart,
+            Command::Pause => &mut self.pause,
+            Command::ScrollUp => &mut self.scroll_up,
+            Command::ScrollDown => &mut self.scroll_down,
+            Command::ScrollLeft => &mut self.scroll_left,
+            Command::ScrollRight => &mut self.scroll_right,
+        };
+        *bound = keys;
+    }
+
+    pub fn command(&self, key: &Key) -> Option<Command> {
+        let name = key_name(key)?;
+
+        Command::ALL.into_iter().find(|command| {
+            self.keys(*command)
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(&name))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bindings {
+    pub mouse: MouseBindings,
+    pub keys: KeyBindings,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_button_can_be_bound() {
+        let mut bindings = MouseBindings::default();
+        bindings.set(mouse::Button::Back, MouseAction::Chord);
+        bindings.set(mouse::Button::Other(8), MouseAction::Flag);
+
+        let saved = toml::to_string(&bindings).unwrap();
+        let loaded: MouseBindings = toml::from_str(&saved).unwrap();
+
+        assert_eq!(loaded, bindings);
+        assert_eq!(loaded.action(mouse::Button::Back), MouseAction::Chord);
+        assert_eq!(loaded.action(mouse::Button::Other(8)), MouseAction::Flag);
+        assert_eq!(loaded.action(mouse::Button::Other(9)), MouseAction::None);
+    }
+
+    #[test]
+    fn missing_buttons_keep_their_defaults() {
+        let loaded: MouseBindings = toml::from_str("forward = \"reveal\"").unwrap();
+
+        assert_eq!(loaded.left, MouseAction::Reveal);
+        assert_eq!(loaded.forward, MouseAction::Reveal);
+        assert!(loaded.other.is_empty());
+    }
+}
@@ -1,46 +1,213 @@
+mod config;
 mod custom_elements;
+mod daily;
+mod error;
 mod globals;
+mod input;
 mod messages;
-mod minesweeper;
+mod network;
+mod presets;
 mod resources;
+mod stats;
+mod storage;
+mod themes;
 
+use error::AppError;
+use iced::keyboard::{self, Key, Modifiers};
+use iced::mouse::{self, ScrollDelta};
 use iced::time::{self, Duration};
+use iced::widget::scrollable::{self, AbsoluteOffset, Viewport};
 use iced::{
-    Element, Length, Size, Subscription, Task, Theme,
-    widget::{column, container},
+    Element, Event, Length, Point, Size, Subscription, Task, Theme, Vector, event,
+    widget::{column, container, image, stack},
     window,
 };
+use input::MouseAction;
 use messages::BoardMessage;
-use minesweeper::{Board, CellPressedState, Vec2};
+use minesweeper_engine::{Board, BoardError, Difficulty, GameState, Vec2};
+use network::{Action, GameSetup, Mode, Move, NetEvent, Packet, Progress, Role};
+use std::sync::OnceLock;
+
+const INITIAL_WINDOW_SIZE: Size =
+    Size::new((globals::SCALE * 8) as f32, (globals::SCALE * 10) as f32);
+
+// Share of the monitor the window can grow to when sized for a board, leaving room for
+// panels and the title bar. Bigger boards scroll inside the window instead
+const MAX_MONITOR_SHARE: f32 = 0.9;
+
+// Used when the platform doesn't say which monitor the window opened on, like on Wayland
+const FALLBACK_MONITOR_SIZE: Size = Size::new(1280.0, 720.0);
+
+// Logical size of the monitor the window opened on. iced only hands it to the function
+// positioning a new window
+static MONITOR_SIZE: OnceLock<Size> = OnceLock::new();
+
+fn centre_window(window: Size, monitor: Size) -> Point {
+    let _ = MONITOR_SIZE.set(monitor);
+
+    Point::new(
+        (monitor.width - window.width) / 2.0,
+        (monitor.height - window.height) / 2.0,
+    )
+}
+
+fn max_window_size() -> Size {
+    MONITOR_SIZE.get().copied().unwrap_or(FALLBACK_MONITOR_SIZE) * MAX_MONITOR_SHARE
+}
 
 fn main() -> iced::Result {
     iced::application(App::title, App::update, App::view)
         .subscription(App::subscription)
         .theme(App::theme)
-        .window_size(Size::new(
-            (globals::SCALE * 8) as f32,
-            (globals::SCALE * 10) as f32,
-        ))
-        .resizable(false)
-        .centered()
-        .run_with(|| (App::new(), Task::none()))
+        .window(window::Settings {
+            size: INITIAL_WINDOW_SIZE,
+            // Room for the new game tiles and the text based screens
+            min_size: Some(Size::new(
+                (globals::SCALE * 8) as f32,
+                (globals::SCALE * 8) as f32,
+            )),
+            position: window::Position::SpecificWith(centre_window),
+            ..window::Settings::default()
+        })
+        .run_with(|| {
+            // The saved difficulty and scale decide the real size
+            let app = App::new();
+            let task = Task::batch([app.resize_to_board(), App::query_scale_factor()]);
+            (app, task)
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Screen {
+    Game,
+    NewGame,
+    CustomGame,
+    Statistics(Option<Difficulty>),
+    Multiplayer,
+    RaceResult,
+    Daily,
+    Settings,
 }
 
 #[derive(Debug, Clone)]
 struct App {
     pub board: Board,
-    pub show_modal: bool,
-    pub timer: usize,
+    pub screen: Screen,
+    pub clicks: usize,
+    pub config: config::Config,
+    pub history: stats::History,
+    pub presets: presets::Presets,
+    pub daily: daily::DailyHistory,
+    // Day of the daily challenge being played, if the current board is one
+    pub daily_game: Option<u64>,
+    pub custom_game: custom_elements::CustomGameForm,
+    pub settings: custom_elements::SettingsForm,
+    pub skin: resources::Skin,
+    // Skins found when the settings were opened
+    pub skins: Vec<String>,
+    // Keyboard cursor, hidden until a key is used
+    pub cursor: Option<Vec2>,
+    pub session: Option<network::Session>,
+    pub multiplayer_address: String,
+    pub multiplayer_mode: Mode,
+    pub error: Option<AppError>,
+    // Of the monitor the window is on, physical pixels per logical pixel
+    pub scale_factor: f32,
+    // Held modifiers, for Ctrl+scroll zooming
+    pub modifiers: Modifiers,
+    // In logical pixels, the board is scaled to fit it
+    pub window_size: Size,
+    // Visible part of a board too big for the window
+    pub viewport: Option<Viewport>,
+    pub mouse_position: Point,
+    // Where the mouse was while dragging the board around with the middle button or
+    // Space held
+    pub pan: Option<Point>,
+    // Set once a drag moved the board, so releasing the button doesn't also play
+    pub panned: bool,
+    pub space_held: bool,
+    // Last mouse position over the minimap, which scrolls the board while held down
+    pub minimap_position: Point,
+    pub minimap_dragging: bool,
+    // The board as the minimap draws it. Only kept while the board is scrolled, a huge
+    // board would otherwise rebuild it on every move for nothing
+    pub minimap: Option<image::Handle>,
     // pub time: u32,
     // pub difficulty: String,
 }
 
 impl App {
     fn new() -> Self {
+        let mut error = None;
+        let mut report = |e: AppError| {
+            error.get_or_insert(e);
+        };
+
+        let config = config::Config::load().unwrap_or_else(|e| {
+            report(e);
+            config::Config::default()
+        });
+        let history = stats::History::load().unwrap_or_else(|e| {
+            report(e);
+            stats::History::default()
+        });
+        let presets = presets::Presets::load().unwrap_or_else(|e| {
+            report(e);
+            presets::Presets::default()
+        });
+        let daily = daily::DailyHistory::load().unwrap_or_else(|e| {
+            report(e);
+            daily::DailyHistory::default()
+        });
+
+        // A hand edited difficulty can be invalid
+        let difficulty = config.difficulty;
+        let board = Board::with_options(
+            difficulty.size_x,
+            difficulty.size_y,
+            difficulty.bomb_count,
+            config.board_options(),
+        )
+        .unwrap_or_else(|e| {
+            report(e.into());
+            Board::new(8, 8, 10).expect("beginner board is always valid")
+        });
+
+        let settings = custom_elements::SettingsForm::new(&config.bindings.keys);
+        let skin = resources::Skin::load(&config.skin).unwrap_or_else(|e| {
+            report(e);
+            resources::Skin::default()
+        });
+
         Self {
-            board: Board::new(8, 8, 10).unwrap(),
-            show_modal: false,
-            timer: 0,
+            board,
+            screen: Screen::Game,
+            clicks: 0,
+            config,
+            history,
+            presets,
+            daily,
+            daily_game: None,
+            custom_game: custom_elements::CustomGameForm::new(Difficulty::BEGINNER),
+            settings,
+            skin,
+            skins: Vec::new(),
+            cursor: None,
+            session: None,
+            multiplayer_address: network::DEFAULT_ADDRESS.to_string(),
+            multiplayer_mode: Mode::default(),
+            error,
+            scale_factor: 1.0,
+            modifiers: Modifiers::default(),
+            window_size: INITIAL_WINDOW_SIZE,
+            viewport: None,
+            mouse_position: Point::ORIGIN,
+            pan: None,
+            panned: false,
+            space_held: false,
+            minimap_position: Point::ORIGIN,
+            minimap_dragging: false,
+            minimap: None,
             // difficulty: "Beginner".to_string(),
         }
     }
@@ -51,100 +218,1112 @@ impl App {
     }
 
     fn theme(&self) -> Theme {
-        Theme::TokyoNight
+        self.config.theme()
     }
 
     fn subscription(&self) -> Subscription<BoardMessage> {
-        return match self.board.state {
-            minesweeper::GameState::Playing => {
-                time::every(Duration::from_secs(1)).map(|_| BoardMessage::Tick)
+        // Only redraws the counter, the board keeps the actual time
+        let timer = match self.board.is_timing() {
+            true => time::every(Duration::from_millis(100)).map(|_| BoardMessage::Tick),
+            false => Subscription::none(),
+        };
+
+        let network = match &self.session {
+            Some(session) => session.subscription().map(BoardMessage::Network),
+            None => Subscription::none(),
+        };
+
+        let auto_pause = match self.board.is_timing() {
+            true => event::listen_with(|event, _, _| match event {
+                Event::Window(window::Event::Unfocused) => Some(BoardMessage::Pause),
+                // Some platforms only report minimising as a resize to nothing
+                Event::Window(window::Event::Resized(size))
+                    if size.width == 0.0 || size.height == 0.0 =>
+                {
+                    Some(BoardMessage::Pause)
+                }
+                _ => None,
+            }),
+            false => Subscription::none(),
+        };
+
+        // Only keys no widget used, so typing in a text input doesn't play the game
+        let keys =
+            keyboard::on_key_press(|key, modifiers| Some(BoardMessage::KeyPressed(key, modifiers)));
+
+        let zoom = event::listen_with(|event, _, _| match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                Some(BoardMessage::ModifiersChanged(modifiers))
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                Some(BoardMessage::WheelScrolled(delta))
+            }
+            // Moving to another monitor can change the scale factor
+            Event::Window(window::Event::Moved(_)) => Some(BoardMessage::WindowMoved),
+            _ => None,
+        });
+
+        let resize = window::resize_events().map(|(_, size)| BoardMessage::WindowResized(size));
+
+        // Events widgets already used count too, pressing the middle button on a cell still
+        // starts a drag
+        let pan = match self.screen == Screen::Game && self.is_scrolled() {
+            true => event::listen_with(|event, _, _| match event {
+                Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    Some(BoardMessage::MouseMoved(position))
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(button)) => {
+                    Some(BoardMessage::MouseDown(button))
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(button)) => {
+                    Some(BoardMessage::MouseUp(button))
+                }
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: Key::Named(keyboard::key::Named::Space),
+                    ..
+                }) => Some(BoardMessage::SpaceHeld(true)),
+                Event::Keyboard(keyboard::Event::KeyReleased {
+                    key: Key::Named(keyboard::key::Named::Space),
+                    ..
+                }) => Some(BoardMessage::SpaceHeld(false)),
+                _ => None,
+            }),
+            false => Subscription::none(),
+        };
+
+        // Polls the skin's files for changes, the default skin is built in
+        let skin = match self.skin.is_default() {
+            true => Subscription::none(),
+            false => time::every(Duration::from_secs(1)).map(|_| BoardMessage::CheckSkin),
+        };
+
+        Subscription::batch([timer, network, auto_pause, keys, zoom, resize, pan, skin])
+    }
+
+    fn is_racing(&self) -> bool {
+        matches!(&self.session, Some(session) if session.mode == Mode::Race)
+    }
+
+    // Rows of cells the game screen needs: the top bar takes two, and races show the
+    // opponents' progress in an extra row below the board
+    fn rows(&self) -> usize {
+        let extra_rows = if self.is_racing() { 1 } else { 0 };
+        2 + extra_rows + self.board.size_y()
+    }
+
+    // The largest whole number of physical pixels per sprite pixel that fits the window,
+    // so sprites stay sharp
+    fn fitted_scale(&self) -> u16 {
+        let sprite_pixels = |cells: usize| cells as f32 * globals::SPRITE_SIZE / self.scale_factor;
+        let fit_x = self.window_size.width / sprite_pixels(self.board.size_x());
+        let fit_y = self.window_size.height / sprite_pixels(self.rows());
+
+        fit_x.min(fit_y).floor() as u16
+    }
+
+    // A zoom picked by the player is kept even if the board then needs scrolling, the
+    // automatic one shrinks the board as far as it goes first
+    fn is_scrolled(&self) -> bool {
+        let smallest = match self.config.scale {
+            Some(_) => self.config.scale(self.scale_factor),
+            None => globals::MIN_SCALE,
+        };
+        self.fitted_scale() < smallest
+    }
+
+    // Boards that don't fit the window are scrolled, at the zoom from the settings
+    fn scale(&self) -> u16 {
+        match self.is_scrolled() {
+            true => self.config.scale(self.scale_factor),
+            false => self.fitted_scale(),
+        }
+    }
+
+    fn pixel_size(&self) -> f32 {
+        globals::pixel_size(self.scale(), self.scale_factor)
+    }
+
+    // Sizes the window for the board at the zoom from the settings. Only done when the
+    // player asks for it, by zooming or picking a board of another size
+    fn resize_to_board(&self) -> Task<BoardMessage> {
+        let pixel_size =
+            globals::pixel_size(self.config.scale(self.scale_factor), self.scale_factor);
+        let cell_size = globals::cell_size(pixel_size);
+
+        let size = Size::new(
+            cell_size * self.board.size_x() as f32,
+            cell_size * self.rows() as f32,
+        );
+
+        // Only known once the window is open, which getting its id waits for
+        window::get_latest().and_then(move |id| {
+            let max = max_window_size();
+            window::resize(
+                id,
+                Size::new(size.width.min(max.width), size.height.min(max.height)),
+            )
+        })
+    }
+
+    fn query_scale_factor() -> Task<BoardMessage> {
+        window::get_latest()
+            .and_then(window::get_scale_factor)
+            .map(BoardMessage::ScaleFactorChanged)
+    }
+
+    // `None` goes back to the automatic scale
+    fn set_scale(&mut self, scale: Option<u16>) -> Task<BoardMessage> {
+        self.config.scale = scale.map(|scale| scale.clamp(globals::MIN_SCALE, globals::MAX_SCALE));
+        self.save_config();
+        self.scrolling_changed();
+
+        match self.screen {
+            Screen::Game => self.resize_to_board(),
+            _ => Task::none(),
+        }
+    }
+
+    // Steps from the size the board is shown at, which the window may have changed
+    fn zoom(&mut self, steps: i16) -> Task<BoardMessage> {
+        let scale = self.scale() as i16 + steps;
+        self.set_scale(Some(scale.max(0) as u16))
+    }
+
+    fn reset_pan(&mut self) {
+        self.pan = None;
+        self.panned = false;
+        self.space_held = false;
+    }
+
+    // Called when the board may have started or stopped scrolling. Only the pan
+    // subscription ends a drag, and it stops once the board fits. A drag left behind would
+    // keep swallowing clicks on the board
+    fn scrolling_changed(&mut self) {
+        if !self.is_scrolled() {
+            self.reset_pan();
+        }
+        if self.minimap.is_none() {
+            self.update_minimap();
+        }
+    }
+
+    fn update_minimap(&mut self) {
+        self.minimap = self
+            .scrolled_viewport()
+            .map(|_| custom_elements::minimap_image(&self.board));
+    }
+
+    fn save_config(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.error = Some(e);
+        }
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.board.elapsed().as_millis() as u64
+    }
+
+    // Called after every move; stores the game once it transitions out of `Playing`
+    fn record_if_finished(&mut self, was_playing: bool) {
+        if !was_playing {
+            return;
+        }
+        let won = match self.board.state() {
+            GameState::Playing => return,
+            GameState::Won => true,
+            GameState::Lost => false,
+        };
+
+        let record = stats::GameRecord {
+            size_x: self.board.size_x(),
+            size_y: self.board.size_y(),
+            bomb_count: self.board.bomb_count(),
+            won,
+            time_ms: self.elapsed_ms(),
+            three_bv: self.board.three_bv(),
+            clicks: self.clicks,
+            date: storage::now(),
+        };
+
+        if let Err(e) = self.history.push(record) {
+            self.error = Some(e);
+        }
+
+        if let Some(day) = self.daily_game.take()
+            && let Err(e) = self.daily.finish(day, won, self.elapsed_ms())
+        {
+            self.error = Some(e);
+        }
+    }
+
+    fn apply_move(&mut self, action: Action, pos: Vec2) -> Result<(), BoardError> {
+        let was_playing = matches!(self.board.state(), GameState::Playing);
+
+        match action {
+            Action::Uncover => self.board.uncover(&pos)?,
+            Action::Flag => self.board.toggle_flag(&pos)?,
+            Action::Chord => self.board.chord(&pos)?,
+        }
+        self.update_minimap();
+
+        self.record_if_finished(was_playing);
+        Ok(())
+    }
+
+    // Moves made on this instance. Clients only ask the host, the board changes once the
+    // host broadcasts the move back
+    fn play(&mut self, action: Action, pos: Vec2) -> Result<(), BoardError> {
+        self.clicks += 1;
+
+        let Some(session) = &self.session else {
+            return self.apply_move(action, pos);
+        };
+
+        let player = session.player;
+        match (session.mode, session.role) {
+            // Every racer has their own board, only the progress is shared
+            (Mode::Race, _) => {
+                self.apply_move(action, pos)?;
+                self.send_progress();
+                Ok(())
+            }
+            (Mode::Coop, Role::Host) => self.host_move(Move {
+                player,
+                action,
+                pos,
+            }),
+            // Until the host's welcome the board isn't the shared one yet
+            (Mode::Coop, Role::Client) => {
+                if session.is_connected() {
+                    session.send(&Packet::Move(Move {
+                        player,
+                        action,
+                        pos,
+                    }));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn host_move(&mut self, played: Move) -> Result<(), BoardError> {
+        self.apply_move(played.action, played.pos)?;
+
+        if let Some(session) = &mut self.session {
+            session.moves.push(played);
+            session.send(&Packet::Move(played));
+        }
+        Ok(())
+    }
+
+    fn send_cursor(&mut self, pos: Option<Vec2>) {
+        if let Some(session) = &self.session
+            && session.shares_cursors()
+        {
+            session.send(&Packet::Cursor {
+                player: session.player,
+                pos,
+            });
+        }
+    }
+
+    fn send_progress(&self) {
+        if let Some(session) = &self.session
+            && session.mode == Mode::Race
+        {
+            session.send(&Packet::Progress {
+                player: session.player,
+                progress: Progress::of(&self.board, self.elapsed_ms()),
+            });
+        }
+    }
+
+    fn set_board(&mut self, board: Board) -> Task<BoardMessage> {
+        let resized =
+            (board.size_x(), board.size_y()) != (self.board.size_x(), self.board.size_y());
+        self.board = board;
+        self.screen = Screen::Game;
+        self.clicks = 0;
+        self.daily_game = None;
+        self.reset_pan();
+        self.update_minimap();
+
+        let (max_x, max_y) = (self.board.size_x() - 1, self.board.size_y() - 1);
+        self.cursor = self
+            .cursor
+            .map(|cursor| Vec2::new(cursor.x.min(max_x), cursor.y.min(max_y)));
+
+        // Lets the other racers know this player is in the new race
+        if let Some(session) = &mut self.session {
+            session.progress.clear();
+        }
+        self.send_progress();
+
+        // Keeps the size the player gave the window when the board size stays the same
+        match resized {
+            true => self.resize_to_board(),
+            false => Task::none(),
+        }
+    }
+
+    fn start_game(&mut self, setup: GameSetup) -> Result<Task<BoardMessage>, BoardError> {
+        Ok(self.set_board(setup.board()?))
+    }
+
+    fn update_network(&mut self, event: NetEvent) -> Result<Task<BoardMessage>, AppError> {
+        let Some(session) = &mut self.session else {
+            return Ok(Task::none());
+        };
+
+        match event {
+            NetEvent::Ready(connection) => session.connection = Some(connection),
+            NetEvent::Failed(e) => {
+                self.session = None;
+                return Err(AppError::Network(e));
+            }
+            NetEvent::Connected(player) => {
+                session.peers.push(player);
+
+                if let Some(connection) = &session.connection {
+                    let welcome = Packet::Welcome {
+                        player,
+                        mode: session.mode,
+                        setup: GameSetup::of(&self.board),
+                        moves: session.moves.clone(),
+                    };
+                    connection.send_to(player, &welcome);
+                }
+            }
+            NetEvent::Disconnected(player) => match session.role {
+                Role::Host => {
+                    session.peers.retain(|peer| *peer != player);
+                    session.cursors.remove(&player);
+                    session.progress.remove(&player);
+                    session.send(&Packet::Left { player });
+                }
+                Role::Client => {
+                    self.session = None;
+                    return Err(AppError::Network("The host ended the game".to_string()));
+                }
+            },
+            NetEvent::Received(from, packet) => return self.receive(from, packet),
+        }
+        Ok(Task::none())
+    }
+
+    fn receive(&mut self, from: usize, packet: Packet) -> Result<Task<BoardMessage>, AppError> {
+        let Some(session) = &mut self.session else {
+            return Ok(Task::none());
+        };
+
+        match (session.role, packet) {
+            (
+                Role::Client,
+                Packet::Welcome {
+                    player,
+                    mode,
+                    setup,
+                    moves,
+                },
+            ) => {
+                session.player = player;
+                session.mode = mode;
+
+                let task = self.start_game(setup)?;
+                for played in moves {
+                    self.apply_move(played.action, played.pos)?;
+                }
+                return Ok(task);
+            }
+            (Role::Client, Packet::NewGame(setup)) => return Ok(self.start_game(setup)?),
+            // Racers play their own boards, a move from anyone else is never theirs
+            (Role::Client, Packet::Move(played)) if session.mode == Mode::Coop => {
+                self.apply_move(played.action, played.pos)?
+            }
+            (Role::Client, Packet::Cursor { player, pos })
+                if player != session.player && session.shares_cursors() =>
+            {
+                session.set_cursor(player, pos);
+            }
+            (Role::Client, Packet::Progress { player, progress }) if player != session.player => {
+                session.progress.insert(player, progress);
+            }
+            (Role::Client, Packet::Left { player }) => {
+                session.cursors.remove(&player);
+                session.progress.remove(&player);
+            }
+            // Clients can't be trusted with their player number, the socket decides it.
+            // Invalid moves from a client are dropped rather than shown to the host, and so
+            // is any move in a race
+            (Role::Host, Packet::Move(played)) if session.mode == Mode::Coop => {
+                let _ = self.host_move(Move {
+                    player: from,
+                    ..played
+                });
+            }
+            (Role::Host, Packet::Cursor { pos, .. }) if session.shares_cursors() => {
+                session.set_cursor(from, pos);
+
+                if let Some(connection) = &session.connection {
+                    connection.send_except(from, &Packet::Cursor { player: from, pos });
+                }
+            }
+            (Role::Host, Packet::Progress { progress, .. }) => {
+                session.progress.insert(from, progress);
+
+                if let Some(connection) = &session.connection {
+                    let packet = Packet::Progress {
+                        player: from,
+                        progress,
+                    };
+                    connection.send_except(from, &packet);
+                }
+            }
+            // Everything else only ever travels from the host to clients
+            _ => {}
+        }
+        Ok(Task::none())
+    }
+
+    // Clients follow the host's games, they can't start their own
+    fn can_start_game(&mut self) -> bool {
+        if let Some(Role::Client) = self.session.as_ref().map(|s| s.role) {
+            self.error = Some(AppError::Network(
+                "Only the host can start a new game".to_string(),
+            ));
+            return false;
+        }
+        true
+    }
+
+    // Starts a game on this instance and on every connected client
+    fn host_board(&mut self, board: Board) -> Task<BoardMessage> {
+        let board = match self.is_racing() {
+            true => GameSetup::race(&board).board().unwrap_or(board),
+            false => board,
+        };
+
+        // The board picked its own seed, which is all clients need to rebuild it
+        if let Some(session) = &mut self.session {
+            session.moves.clear();
+            session.send(&Packet::NewGame(GameSetup::of(&board)));
+        }
+        self.set_board(board)
+    }
+
+    // Same size, mines and seed. A daily challenge in progress can't be restarted, that
+    // would be a second attempt
+    fn restart(&mut self) -> Task<BoardMessage> {
+        if self.daily_game.is_some() || !self.can_start_game() {
+            return Task::none();
+        }
+
+        match GameSetup::of(&self.board).board() {
+            Ok(board) => self.host_board(board),
+            Err(e) => {
+                self.error = Some(e.into());
+                Task::none()
             }
-            _ => Subscription::none(),
+        }
+    }
+
+    fn show_race_result(&mut self, was_playing: bool) -> Task<BoardMessage> {
+        if was_playing && self.board.state() != GameState::Playing && self.is_racing() {
+            self.screen = Screen::RaceResult;
+        }
+        Task::none()
+    }
+
+    fn run_command(&mut self, command: input::Command) -> Task<BoardMessage> {
+        // Paging the view works without the keyboard cursor
+        let page = match command {
+            input::Command::ScrollUp => Some((0.0, -1.0)),
+            input::Command::ScrollDown => Some((0.0, 1.0)),
+            input::Command::ScrollLeft => Some((-1.0, 0.0)),
+            input::Command::ScrollRight => Some((1.0, 0.0)),
+            _ => None,
+        };
+        if let Some((x, y)) = page {
+            return self.scroll_page(x, y);
+        }
+
+        let max_x = self.board.size_x() - 1;
+        let max_y = self.board.size_y() - 1;
+
+        // The first key press only shows the cursor, in the middle of the board
+        let Some(cursor) = self.cursor else {
+            self.cursor = Some(Vec2::new(max_x / 2, max_y / 2));
+            self.send_cursor(self.cursor);
+            return Task::none();
+        };
+
+        let (x, y) = (cursor.x, cursor.y);
+        let was_playing = matches!(self.board.state(), GameState::Playing);
+
+        let result = match command {
+            input::Command::Up => return self.move_cursor(x, y.saturating_sub(1)),
+            input::Command::Down => return self.move_cursor(x, (y + 1).min(max_y)),
+            input::Command::Left => return self.move_cursor(x.saturating_sub(1), y),
+            input::Command::Right => return self.move_cursor((x + 1).min(max_x), y),
+            input::Command::Reveal => self.play(Action::Uncover, cursor),
+            input::Command::Flag => self.play(Action::Flag, cursor),
+            input::Command::Chord => self.play(Action::Chord, cursor),
+            input::Command::NewGame => return self.update(BoardMessage::OpenNewGameModal),
+            input::Command::Restart => return self.restart(),
+            input::Command::Pause => {
+                self.board.pause();
+                return Task::none();
+            }
+            // Paged above
+            input::Command::ScrollUp
+            | input::Command::ScrollDown
+            | input::Command::ScrollLeft
+            | input::Command::ScrollRight => return Task::none(),
+        };
+
+        if let Err(e) = result {
+            self.error = Some(e.into());
+        }
+        self.show_race_result(was_playing)
+    }
+
+    fn move_cursor(&mut self, x: usize, y: usize) -> Task<BoardMessage> {
+        self.cursor = Some(Vec2::new(x, y));
+        self.send_cursor(self.cursor);
+        self.scroll_to_cell(Vec2::new(x, y))
+    }
+
+    // The visible part of the board, if it is too big for the window
+    fn scrolled_viewport(&self) -> Option<Viewport> {
+        self.viewport.filter(|_| self.is_scrolled())
+    }
+
+    // Scrolls by whole pages, negative values go up or left
+    fn scroll_page(&self, x: f32, y: f32) -> Task<BoardMessage> {
+        let Some(viewport) = self.scrolled_viewport() else {
+            return Task::none();
+        };
+        let bounds = viewport.bounds();
+
+        scrollable::scroll_by(
+            custom_elements::viewport_id(),
+            AbsoluteOffset {
+                x: x * bounds.width,
+                y: y * bounds.height,
+            },
+        )
+    }
+
+    // Scrolls just far enough to show the cell, so the keyboard cursor stays in view
+    fn scroll_to_cell(&self, pos: Vec2) -> Task<BoardMessage> {
+        let Some(viewport) = self.scrolled_viewport() else {
+            return Task::none();
+        };
+        let cell_size = globals::cell_size(self.pixel_size());
+        let (bounds, offset) = (viewport.bounds(), viewport.absolute_offset());
+
+        let follow = |offset: f32, cell: usize, visible: f32| {
+            let start = cell as f32 * cell_size;
+            offset.max(start + cell_size - visible).min(start)
         };
+
+        scrollable::scroll_to(
+            custom_elements::viewport_id(),
+            AbsoluteOffset {
+                x: follow(offset.x, pos.x, bounds.width),
+                y: follow(offset.y, pos.y, bounds.height),
+            },
+        )
+    }
+
+    // Centres the viewport on the cell under the mouse on the minimap
+    fn jump_to_minimap(&self) -> Task<BoardMessage> {
+        let Some(viewport) = self.scrolled_viewport() else {
+            return Task::none();
+        };
+        let cell = custom_elements::minimap_cell(&self.board, self.minimap_position);
+        let cell_size = globals::cell_size(self.pixel_size());
+        let bounds = viewport.bounds();
+
+        scrollable::scroll_to(
+            custom_elements::viewport_id(),
+            AbsoluteOffset {
+                x: (cell.x * cell_size - bounds.width / 2.0).max(0.0),
+                y: (cell.y * cell_size - bounds.height / 2.0).max(0.0),
+            },
+        )
+    }
+
+    fn update_pan(&mut self, message: BoardMessage) -> Task<BoardMessage> {
+        match message {
+            BoardMessage::BoardScrolled(viewport) => {
+                self.viewport = Some(viewport);
+                self.scrolling_changed();
+            }
+            BoardMessage::MouseMoved(position) => {
+                self.mouse_position = position;
+
+                if let Some(last) = self.pan.replace(position) {
+                    let delta = last - position;
+                    self.panned |= delta != Vector::ZERO;
+
+                    return scrollable::scroll_by(
+                        custom_elements::viewport_id(),
+                        AbsoluteOffset {
+                            x: delta.x,
+                            y: delta.y,
+                        },
+                    );
+                }
+            }
+            BoardMessage::MouseDown(button) => {
+                self.panned = false;
+
+                if button == mouse::Button::Middle || self.space_held {
+                    self.pan = Some(self.mouse_position);
+                }
+            }
+            // Left is the button dragging with Space held, or on the minimap
+            BoardMessage::MouseUp(mouse::Button::Middle | mouse::Button::Left) => {
+                self.pan = None;
+                self.minimap_dragging = false;
+            }
+            BoardMessage::SpaceHeld(held) => self.space_held = held,
+            BoardMessage::MinimapMoved(position) => {
+                self.minimap_position = position;
+
+                if self.minimap_dragging {
+                    return self.jump_to_minimap();
+                }
+            }
+            BoardMessage::MinimapPressed => {
+                self.minimap_dragging = true;
+                return self.jump_to_minimap();
+            }
+            BoardMessage::MinimapReleased => self.minimap_dragging = false,
+            _ => {}
+        }
+        Task::none()
+    }
+
+    fn update_cell(&mut self, message: BoardMessage) -> Result<(), BoardError> {
+        match message {
+            // Space turns the mouse into a hand for dragging the board, and the end of a
+            // drag isn't a click
+            BoardMessage::CellRelease(..) if self.space_held || self.panned => {}
+            BoardMessage::CellPointed(pos) => self.send_cursor(pos),
+            BoardMessage::CellRelease(button, pos_x, pos_y) => {
+                let pos = Vec2::new(pos_x, pos_y);
+
+                match self.config.bindings.mouse.action(button) {
+                    MouseAction::Reveal => self.play(Action::Uncover, pos)?,
+                    MouseAction::Flag => self.play(Action::Flag, pos)?,
+                    MouseAction::Chord => self.play(Action::Chord, pos)?,
+                    MouseAction::None => {}
+                }
+            }
+            _ => {}
+        }
+        Ok(())
     }
 
     fn update(&mut self, message: BoardMessage) -> Task<BoardMessage> {
         match message {
             // Cell
-            BoardMessage::CellHover(pos_x, pos_y) => {
-                self.board
-                    .get_mut_cell(&Vec2 { x: pos_x, y: pos_y })
-                    .pressed_state = CellPressedState::Hovered;
+            BoardMessage::CellPointed(..) | BoardMessage::CellRelease(..) => {
+                let was_playing = matches!(self.board.state(), GameState::Playing);
+
+                if let Err(e) = self.update_cell(message) {
+                    self.error = Some(e.into());
+                }
+
+                return self.show_race_result(was_playing);
+            }
+
+            BoardMessage::KeyPressed(key, modifiers) => {
+                // `=` shares a key with `+` on most layouts
+                if modifiers.control() {
+                    return match key.as_ref() {
+                        Key::Character("+" | "=") => self.zoom(1),
+                        Key::Character("-") => self.zoom(-1),
+                        Key::Character("0") => self.set_scale(None),
+                        _ => Task::none(),
+                    };
+                }
+                // Leaves other Ctrl and Alt combinations to the system
+                if modifiers.alt() || modifiers.logo() {
+                    return Task::none();
+                }
+                // Any key brings a paused board back
+                if self.board.is_paused() {
+                    self.board.resume();
+                    return Task::none();
+                }
+
+                match self.screen {
+                    // Space is for dragging boards too big for the window around
+                    Screen::Game
+                        if self.is_scrolled() && key == Key::Named(keyboard::key::Named::Space) => {
+                    }
+                    Screen::Game => {
+                        if let Some(command) = self.config.bindings.keys.command(&key) {
+                            return self.run_command(command);
+                        }
+                    }
+                    // The new game tiles in reading order
+                    Screen::NewGame => {
+                        let message = match key.as_ref() {
+                            Key::Character("1") => BoardMessage::SubmitNewGame(8, 8, 10),
+                            Key::Character("2") => BoardMessage::SubmitNewGame(16, 16, 40),
+                            Key::Character("3") => BoardMessage::SubmitNewGame(30, 16, 99),
+                            Key::Character("4") => BoardMessage::OpenCustomGame,
+                            _ => return Task::none(),
+                        };
+                        return self.update(message);
+                    }
+                    _ => {}
+                }
+            }
+
+            BoardMessage::BoardScrolled(..)
+            | BoardMessage::MouseMoved(..)
+            | BoardMessage::MouseDown(..)
+            | BoardMessage::MouseUp(..)
+            | BoardMessage::SpaceHeld(..)
+            | BoardMessage::MinimapMoved(..)
+            | BoardMessage::MinimapPressed
+            | BoardMessage::MinimapReleased => return self.update_pan(message),
+
+            BoardMessage::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+            BoardMessage::WheelScrolled(delta) => {
+                let (ScrollDelta::Lines { y, .. } | ScrollDelta::Pixels { y, .. }) = delta;
+
+                if self.modifiers.control() && y != 0.0 {
+                    return self.zoom(y.signum() as i16);
+                }
             }
-            BoardMessage::CellUnhover(pos_x, pos_y) => {
-                self.board
-                    .get_mut_cell(&Vec2 { x: pos_x, y: pos_y })
-                    .pressed_state = CellPressedState::None;
+            BoardMessage::ScaleFactorChanged(scale_factor) => {
+                self.scale_factor = scale_factor;
+                self.scrolling_changed();
             }
-            BoardMessage::CellPress(pos_x, pos_y) => {
-                self.board
-                    .get_mut_cell(&Vec2 { x: pos_x, y: pos_y })
-                    .pressed_state = CellPressedState::Pressed;
+            BoardMessage::WindowResized(size) => {
+                self.window_size = size;
+                self.scrolling_changed();
             }
-            BoardMessage::CellLeftClick(pos_x, pos_y) => {
-                let pos = &Vec2 { x: pos_x, y: pos_y };
-                let cell = self.board.get_cell(pos);
+            BoardMessage::WindowMoved => return App::query_scale_factor(),
 
-                if let CellPressedState::Pressed = cell.pressed_state {
-                    self.board.uncover(pos);
+            BoardMessage::Tick => {}
+            BoardMessage::Pause => self.board.pause(),
+            BoardMessage::Resume => self.board.resume(),
+
+            // New game
+            BoardMessage::OpenNewGameModal => self.screen = Screen::NewGame,
+            BoardMessage::SubmitNewGame(size_x, size_y, bomb_count) => {
+                if !self.can_start_game() {
+                    return Task::none();
                 }
 
-                self.board
-                    .get_mut_cell(&Vec2 { x: pos_x, y: pos_y })
-                    .pressed_state = CellPressedState::None;
+                let options = self.config.board_options();
+                let board = match Board::with_options(size_x, size_y, bomb_count, options) {
+                    Ok(board) => board,
+                    Err(e) => {
+                        self.error = Some(e.into());
+                        return Task::none();
+                    }
+                };
+
+                self.config.difficulty = Difficulty::new(size_x, size_y, bomb_count);
+                self.save_config();
+
+                return self.host_board(board);
             }
-            BoardMessage::CellRightClick(pos_x, pos_y) => {
-                self.board
-                    .get_mut_cell(&Vec2 { x: pos_x, y: pos_y })
-                    .toggle_flagged();
+
+            // Custom game
+            BoardMessage::OpenCustomGame => {
+                self.screen = Screen::CustomGame;
+                self.custom_game = custom_elements::CustomGameForm::new(self.board.difficulty());
             }
+            BoardMessage::CustomWidthChanged(width) => self.custom_game.width = width,
+            BoardMessage::CustomHeightChanged(height) => self.custom_game.height = height,
+            BoardMessage::CustomMinesChanged(mines) => self.custom_game.mines = mines,
+            BoardMessage::CustomDensityChanged(density) => self.custom_game.set_density(density),
+            BoardMessage::CustomPresetNameChanged(name) => self.custom_game.preset_name = name,
+            BoardMessage::SavePreset => {
+                if let Ok(difficulty) = self.custom_game.difficulty(self.config.first_click) {
+                    let preset = presets::Preset {
+                        name: self.custom_game.preset_name.trim().to_string(),
+                        size_x: difficulty.size_x,
+                        size_y: difficulty.size_y,
+                        bomb_count: difficulty.bomb_count,
+                    };
 
-            BoardMessage::Tick => {
-                self.timer += 1;
+                    if let Err(e) = self.presets.insert(preset) {
+                        self.error = Some(e);
+                    }
+                    self.custom_game.preset_name.clear();
+                }
+            }
+            BoardMessage::DeletePreset(name) => {
+                if let Err(e) = self.presets.remove(&name) {
+                    self.error = Some(e);
+                }
             }
 
-            // New game
-            BoardMessage::OpenNewGameModal => {
-                self.show_modal = true;
-
-                return window::get_latest().and_then(move |id| {
-                    window::resize(
-                        id,
-                        Size::new((globals::SCALE * 8) as f32, (globals::SCALE * 8) as f32),
-                    )
-                });
+            BoardMessage::DismissError => self.error = None,
+
+            // Settings, saved as soon as they change
+            BoardMessage::OpenSettings => {
+                self.screen = Screen::Settings;
+                self.settings = custom_elements::SettingsForm::new(&self.config.bindings.keys);
+                self.skins = resources::Skin::list();
+            }
+            BoardMessage::FirstClickChanged(first_click) => {
+                self.config.first_click = first_click;
+                self.save_config();
+            }
+            BoardMessage::QuestionMarksToggled(question_marks) => {
+                self.config.question_marks = question_marks;
+                self.save_config();
+            }
+            BoardMessage::ScaleChanged(scale) => return self.set_scale(Some(scale)),
+            BoardMessage::AutoScaleToggled(automatic) => {
+                let scale = self.config.scale(self.scale_factor);
+                return self.set_scale((!automatic).then_some(scale));
+            }
+            BoardMessage::ThemeChanged(theme) => {
+                self.config.theme = theme.to_string();
+                self.save_config();
+            }
+            BoardMessage::SkinChanged(name) => match resources::Skin::load(&name) {
+                Ok(skin) => {
+                    self.skin = skin;
+                    self.config.skin = name;
+                    self.save_config();
+                }
+                Err(e) => self.error = Some(e),
+            },
+            // A skin that fails to reload keeps its old sprites until the next change
+            BoardMessage::CheckSkin => {
+                if self.skin.poll_changes() {
+                    match resources::Skin::load(&self.skin.name) {
+                        Ok(skin) => self.skin = skin,
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+            }
+            BoardMessage::MouseBindingChanged(button, action) => {
+                self.config.bindings.mouse.set(button, action);
+                self.save_config();
+            }
+            BoardMessage::KeyBindingChanged(command, keys) => {
+                let bound = custom_elements::parse_keys(&keys);
+                self.config.bindings.keys.set(command, bound);
+                self.save_config();
+
+                if let Some(index) = input::Command::ALL.iter().position(|c| *c == command) {
+                    self.settings.keys[index] = keys;
+                }
             }
-            BoardMessage::SubmitNewGame(size_x, size_y, bomb_count) => {
-                self.board = Board::new(size_x, size_y, bomb_count).unwrap();
-                self.show_modal = false;
-                self.timer = 0;
 
-                let size = Size::new(
-                    (globals::SCALE * self.board.size_x as u16) as f32,
-                    (globals::SCALE * (2 + self.board.size_y as u16)) as f32,
-                );
+            // Multiplayer
+            BoardMessage::OpenMultiplayer => {
+                self.screen = Screen::Multiplayer;
+            }
+            BoardMessage::MultiplayerAddressChanged(address) => self.multiplayer_address = address,
+            BoardMessage::MultiplayerModeChanged(mode) => self.multiplayer_mode = mode,
+            BoardMessage::HostGame => {
+                // Other players' moves would count towards the daily attempt, and a race
+                // would hand out a second attempt at the same board
+                if self.daily_game.is_some() {
+                    self.error = Some(AppError::Network(
+                        "Finish the daily challenge before hosting a game".to_string(),
+                    ));
+                    return Task::none();
+                }
+
+                self.session = Some(network::Session::new(
+                    Role::Host,
+                    self.multiplayer_mode,
+                    self.multiplayer_address.trim().to_string(),
+                ));
+
+                // Racers are welcomed with the current board, so it has to be a race board
+                if self.is_racing()
+                    && let Ok(board) = GameSetup::race(&self.board).board()
+                {
+                    // The host stays on this screen to see the players join
+                    let task = self.set_board(board);
+                    self.screen = Screen::Multiplayer;
+                    return task;
+                }
+            }
+            BoardMessage::JoinGame => {
+                self.session = Some(network::Session::new(
+                    Role::Client,
+                    Mode::default(),
+                    self.multiplayer_address.trim().to_string(),
+                ));
+            }
+            BoardMessage::LeaveGame => {
+                if let Some(session) = self.session.take() {
+                    session.close();
+                }
+            }
+            BoardMessage::Network(event) => match self.update_network(event) {
+                Ok(task) => return task,
+                Err(e) => self.error = Some(e),
+            },
 
-                return window::get_latest().and_then(move |id| window::resize(id, size));
+            // Daily challenge
+            BoardMessage::OpenDaily => {
+                self.screen = Screen::Daily;
             }
+            BoardMessage::PlayDaily => {
+                let day = daily::today();
+                if self.daily.result(day).is_some() || self.session.is_some() {
+                    return Task::none();
+                }
+
+                let challenge = daily::Challenge::of(day);
+                let difficulty = challenge.difficulty;
+                let board = match Board::with_options(
+                    difficulty.size_x,
+                    difficulty.size_y,
+                    difficulty.bomb_count,
+                    challenge.options(self.config.question_marks),
+                ) {
+                    Ok(board) => board,
+                    Err(e) => {
+                        self.error = Some(e.into());
+                        return Task::none();
+                    }
+                };
+
+                if let Err(e) = self.daily.start(day) {
+                    self.error = Some(e);
+                }
+
+                let task = self.set_board(board);
+                self.daily_game = Some(day);
+                return task;
+            }
+
+            // Statistics
+            BoardMessage::OpenStatistics => {
+                self.screen = Screen::Statistics(None);
+            }
+            BoardMessage::SelectStatistics(difficulty) => {
+                self.screen = Screen::Statistics(difficulty);
+            }
+            BoardMessage::CloseScreen => self.screen = Screen::Game,
         }
         Task::none()
     }
 
     fn view(&self) -> Element<'_, BoardMessage> {
-        let mut content = column![
-            custom_elements::top_bar(&self.board.state, self.timer),
-            container(custom_elements::grid(&self.board))
-                .width(Length::Fill)
-                .center_x(Length::Fill)
-        ];
+        let content = match self.screen {
+            Screen::Game => {
+                let pixel_size = self.pixel_size();
 
-        if self.show_modal {
-            content = column![custom_elements::new_game()]
+                // The board is hidden while paused so the pause can't be used to think
+                let board = match self.board.is_paused() {
+                    true => custom_elements::paused(
+                        self.board.size_x(),
+                        self.board.size_y(),
+                        pixel_size,
+                    ),
+                    false => custom_elements::grid(
+                        &self.board,
+                        self.cursor,
+                        self.session
+                            .as_ref()
+                            .filter(|s| s.shares_cursors())
+                            .map(|s| &s.cursors),
+                        pixel_size,
+                        &self.skin,
+                        &self.config.bindings.mouse,
+                        self.space_held,
+                    ),
+                };
+                let board = match (self.scrolled_viewport(), &self.minimap) {
+                    // The minimap would give away a paused board too
+                    (Some(viewport), Some(minimap)) if !self.board.is_paused() => stack![
+                        custom_elements::viewport(board),
+                        container(custom_elements::minimap(
+                            minimap.clone(),
+                            &self.board,
+                            viewport,
+                            globals::cell_size(pixel_size)
+                        ))
+                        .align_right(Length::Fill)
+                        .align_bottom(Length::Fill)
+                        .padding(16)
+                    ]
+                    .into(),
+                    _ if self.is_scrolled() => custom_elements::viewport(board),
+                    // Centred in whatever room the window leaves around the board
+                    _ => container(board).center(Length::Fill).into(),
+                };
+
+                let game = column![
+                    custom_elements::top_bar(
+                        self.board.state(),
+                        self.board.bomb_count() as isize - self.board.flag_count() as isize,
+                        self.board.elapsed().as_secs() as usize,
+                        pixel_size,
+                        &self.skin
+                    ),
+                    board
+                ];
+
+                match &self.session {
+                    Some(session) if session.mode == Mode::Race => game.push(
+                        custom_elements::race_progress(&session.progress, pixel_size),
+                    ),
+                    _ => game,
+                }
+            }
+            Screen::NewGame => column![custom_elements::new_game(&self.skin)],
+            Screen::CustomGame => {
+                column![custom_elements::custom_game(
+                    &self.custom_game,
+                    &self.presets,
+                    self.config.first_click
+                )]
+            }
+            Screen::Statistics(filter) => {
+                column![custom_elements::statistics(&self.history, filter)]
+            }
+            Screen::Multiplayer => column![custom_elements::multiplayer(
+                &self.multiplayer_address,
+                self.multiplayer_mode,
+                self.session.as_ref()
+            )],
+            Screen::Daily => column![custom_elements::daily(
+                &self.daily,
+                daily::today(),
+                self.session.is_none()
+            )],
+            Screen::Settings => column![custom_elements::settings(
+                &self.config,
+                &self.settings,
+                self.config.scale(self.scale_factor),
+                &self.skins
+            )],
+            Screen::RaceResult => match &self.session {
+                Some(session) => column![custom_elements::race_result(
+                    session,
+                    Progress::of(&self.board, self.elapsed_ms())
+                )],
+                None => column![custom_elements::new_game(&self.skin)],
+            },
         };
 
-        container(content)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+        let content = container(content).width(Length::Fill).height(Length::Fill);
+
+        match &self.error {
+            Some(error) => stack![content, custom_elements::error_banner(error)].into(),
+            None => content.into(),
+        }
     }
 }
@@ -1,15 +1,77 @@
-#[derive(Debug, Clone, Copy)]
-pub enum BoardMessage {
-    CellLeftClick(usize, usize),
-    CellRightClick(usize, usize),
-
-    CellHover(usize, usize),
-    CellUnhover(usize, usize),
+use crate::input::{Command, MouseAction};
+use crate::network::{Mode, NetEvent};
+use iced::keyboard::{Key, Modifiers};
+use iced::mouse::{self, ScrollDelta};
+use iced::widget::scrollable::Viewport;
+use iced::{Point, Size, Theme};
+use minesweeper_engine::{Difficulty, FirstClickPolicy, Vec2};
 
-    CellPress(usize, usize),
+#[derive(Debug, Clone)]
+pub enum BoardMessage {
+    // Which action a button triggers is decided by the bindings
+    CellRelease(mouse::Button, usize, usize),
+    // The cell under the mouse, only sent in multiplayer games to show the other players
+    CellPointed(Option<Vec2>),
 
     OpenNewGameModal,
     SubmitNewGame(usize, usize, usize),
 
+    OpenCustomGame,
+    CustomWidthChanged(String),
+    CustomHeightChanged(String),
+    CustomMinesChanged(String),
+    CustomDensityChanged(f32),
+    FirstClickChanged(FirstClickPolicy),
+    CustomPresetNameChanged(String),
+    SavePreset,
+    DeletePreset(String),
+
+    OpenSettings,
+    QuestionMarksToggled(bool),
+    ScaleChanged(u16),
+    AutoScaleToggled(bool),
+    ThemeChanged(Theme),
+    SkinChanged(String),
+    CheckSkin,
+    MouseBindingChanged(mouse::Button, MouseAction),
+    KeyBindingChanged(Command, String),
+
+    OpenDaily,
+    PlayDaily,
+
+    OpenStatistics,
+    SelectStatistics(Option<Difficulty>),
+    CloseScreen,
+
+    OpenMultiplayer,
+    MultiplayerAddressChanged(String),
+    MultiplayerModeChanged(Mode),
+    HostGame,
+    JoinGame,
+    LeaveGame,
+    Network(NetEvent),
+
+    DismissError,
+
+    KeyPressed(Key, Modifiers),
+    ModifiersChanged(Modifiers),
+    WheelScrolled(ScrollDelta),
+
+    ScaleFactorChanged(f32),
+    WindowResized(Size),
+
+    // Scrolling and dragging boards bigger than the window
+    BoardScrolled(Viewport),
+    MouseMoved(Point),
+    MouseDown(mouse::Button),
+    MouseUp(mouse::Button),
+    SpaceHeld(bool),
+    MinimapMoved(Point),
+    MinimapPressed,
+    MinimapReleased,
+    WindowMoved,
+
     Tick,
+    Pause,
+    Resume,
 }
@@ -0,0 +1,462 @@
+use iced::Subscription;
+use iced::futures::channel::mpsc::{self, UnboundedSender};
+use iced::futures::{SinkExt, Stream, StreamExt};
+use minesweeper_engine::{
+    Board, BoardError, BoardOptions, Difficulty, FirstClickPolicy, GameState, Vec2,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+// The host is always player 0, clients are numbered in the order they join
+pub const HOST: usize = 0;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+// Packets waiting for a peer before it counts as stuck and is dropped. Far more than a
+// burst of moves and cursor updates, which its writer thread sends as fast as it can
+const OUTBOX_SIZE: usize = 1024;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mode {
+    // Everyone plays on the host's board
+    #[default]
+    Coop,
+    // Everyone gets their own copy of the same board, fastest to clear it wins
+    Race,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 2] = [Mode::Coop, Mode::Race];
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Mode::Coop => "Co-op",
+            Mode::Race => "Race",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Uncover,
+    Flag,
+    Chord,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Move {
+    pub player: usize,
+    pub action: Action,
+    pub pos: Vec2,
+}
+
+// Everything needed to build the same board on every instance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GameSetup {
+    pub difficulty: Difficulty,
+    pub first_click: FirstClickPolicy,
+    pub seed: Option<u64>,
+    // The host's setting, so flagging a flagged cell does the same on every instance
+    #[serde(default)]
+    pub question_marks: bool,
+}
+
+impl GameSetup {
+    pub fn of(board: &Board) -> Self {
+        Self {
+            difficulty: board.difficulty(),
+            first_click: board.first_click(),
+            seed: Some(board.seed()),
+            question_marks: board.options().question_marks,
+        }
+    }
+
+    // Safe and opening first clicks move mines away from wherever each racer clicks first,
+    // which would leave everyone on a different board
+    pub fn race(board: &Board) -> Self {
+        Self {
+            first_click: FirstClickPolicy::Any,
+            ..Self::of(board)
+        }
+    }
+
+    pub fn board(&self) -> Result<Board, BoardError> {
+        let options = BoardOptions {
+            first_click: self.first_click,
+            seed: self.seed,
+            question_marks: self.question_marks,
+        };
+
+        Board::with_options(
+            self.difficulty.size_x,
+            self.difficulty.size_y,
+            self.difficulty.bomb_count,
+            options,
+        )
+    }
+}
+
+// How far a player got in a race
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Progress {
+    pub cleared: f32,
+    pub state: GameState,
+    pub time_ms: u64,
+}
+
+impl Progress {
+    pub fn of(board: &Board, time_ms: u64) -> Self {
+        Self {
+            cleared: board.progress(),
+            state: board.state(),
+            time_ms,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Packet {
+    // Sent by the host to a client that just connected, with every move made so far
+    Welcome {
+        player: usize,
+        mode: Mode,
+        setup: GameSetup,
+        moves: Vec<Move>,
+    },
+    NewGame(GameSetup),
+    // From a client this is a request, from the host it is the authoritative result
+    Move(Move),
+    Cursor {
+        player: usize,
+        pos: Option<Vec2>,
+    },
+    Progress {
+        player: usize,
+        progress: Progress,
+    },
+    Left {
+        player: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum NetEvent {
+    Ready(Connection),
+    Connected(usize),
+    Received(usize, Packet),
+    Disconnected(usize),
+    Failed(String),
+}
+
+struct Peer {
+    id: usize,
+    // Only for shutting the socket down, its writer thread owns another handle
+    stream: TcpStream,
+    outbox: SyncSender<Arc<str>>,
+}
+
+// Write halves of every open socket. The host holds one per client, a client only the host
+#[derive(Clone, Default)]
+pub struct Connection {
+    peers: Arc<Mutex<Vec<Peer>>>,
+    closed: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for Connection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Connection")
+            .field("closed", &self.closed.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Connection {
+    fn add(&self, id: usize, stream: TcpStream) -> std::io::Result<()> {
+        let writer = stream.try_clone()?;
+        let (outbox, queue) = sync_channel(OUTBOX_SIZE);
+        std::thread::spawn(move || write_packets(writer, queue));
+
+        self.peers.lock().unwrap().push(Peer { id, stream, outbox });
+        Ok(())
+    }
+
+    fn remove(&self, id: usize) {
+        self.peers.lock().unwrap().retain(|peer| peer.id != id);
+    }
+
+    // Only queues the packet, so a peer that stops reading can't freeze the game. One that
+    // falls too far behind is disconnected, its reader thread then reports it gone
+    fn send_where(&self, packet: &Packet, filter: impl Fn(usize) -> bool) {
+        let Ok(mut line) = serde_json::to_string(packet) else {
+            return;
+        };
+        line.push('\n');
+        let line: Arc<str> = line.into();
+
+        for peer in self.peers.lock().unwrap().iter() {
+            if filter(peer.id) && peer.outbox.try_send(line.clone()).is_err() {
+                let _ = peer.stream.shutdown(Shutdown::Both);
+            }
+        }
+    }
+
+    pub fn send(&self, packet: &Packet) {
+        self.send_where(packet, |_| true);
+    }
+
+    pub fn send_to(&self, id: usize, packet: &Packet) {
+        self.send_where(packet, |peer| peer == id);
+    }
+
+    pub fn send_except(&self, id: usize, packet: &Packet) {
+        self.send_where(packet, |peer| peer != id);
+    }
+
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        for peer in self.peers.lock().unwrap().drain(..) {
+            let _ = peer.stream.shutdown(Shutdown::Both);
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+}
+
+// Ends once the peer is removed, or a write fails because it is gone
+fn write_packets(mut stream: TcpStream, queue: Receiver<Arc<str>>) {
+    for line in queue {
+        if stream.write_all(line.as_bytes()).is_err() {
+            break;
+        }
+    }
+}
+
+fn read_packets(
+    id: usize,
+    stream: TcpStream,
+    connection: Connection,
+    events: UnboundedSender<NetEvent>,
+) {
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        // Unknown packets are skipped so a newer peer doesn't drop the connection
+        if let Ok(packet) = serde_json::from_str(&line)
+            && events
+                .unbounded_send(NetEvent::Received(id, packet))
+                .is_err()
+        {
+            break;
+        }
+    }
+
+    connection.remove(id);
+    if !connection.is_closed() {
+        let _ = events.unbounded_send(NetEvent::Disconnected(id));
+    }
+}
+
+fn run_host(address: String, events: UnboundedSender<NetEvent>) {
+    let listener = match TcpListener::bind(&address).and_then(|listener| {
+        listener.set_nonblocking(true)?;
+        Ok(listener)
+    }) {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = events.unbounded_send(NetEvent::Failed(format!(
+                "Could not host on {}: {}",
+                address, e
+            )));
+            return;
+        }
+    };
+
+    let connection = Connection::default();
+    let _ = events.unbounded_send(NetEvent::Ready(connection.clone()));
+
+    let mut next_id = HOST + 1;
+
+    // Polled so the port is released once the session ends
+    while !connection.is_closed() && !events.is_closed() {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL);
+                continue;
+            }
+            Err(_) => continue,
+        };
+
+        let Ok(writer) = stream
+            .set_nonblocking(false)
+            .and_then(|_| stream.try_clone())
+        else {
+            continue;
+        };
+
+        let id = next_id;
+        if connection.add(id, writer).is_err() {
+            continue;
+        }
+        next_id += 1;
+
+        let _ = events.unbounded_send(NetEvent::Connected(id));
+
+        let (connection, events) = (connection.clone(), events.clone());
+        std::thread::spawn(move || read_packets(id, stream, connection, events));
+    }
+}
+
+fn run_client(address: String, events: UnboundedSender<NetEvent>) {
+    let connection = Connection::default();
+
+    let stream = address
+        .to_socket_addrs()
+        .and_then(|mut addresses| {
+            addresses.next().ok_or(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "no address found",
+            ))
+        })
+        .and_then(|socket| TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT))
+        .and_then(|stream| {
+            connection.add(HOST, stream.try_clone()?)?;
+            Ok(stream)
+        });
+
+    let stream = match stream {
+        Ok(stream) => stream,
+        Err(e) => {
+            let _ = events.unbounded_send(NetEvent::Failed(format!(
+                "Could not join {}: {}",
+                address, e
+            )));
+            return;
+        }
+    };
+
+    let _ = events.unbounded_send(NetEvent::Ready(connection.clone()));
+
+    read_packets(HOST, stream, connection, events);
+}
+
+// Sockets are blocking, so they live on their own threads and forward events into the
+// subscription's stream
+fn spawn_stream(
+    run: fn(String, UnboundedSender<NetEvent>),
+    address: String,
+) -> impl Stream<Item = NetEvent> {
+    iced::stream::channel(100, move |mut output| async move {
+        let (sender, mut receiver) = mpsc::unbounded();
+        std::thread::spawn(move || run(address, sender));
+
+        while let Some(event) = receiver.next().await {
+            if output.send(event).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+pub fn host(address: String) -> impl Stream<Item = NetEvent> {
+    spawn_stream(run_host, address)
+}
+
+pub fn join(address: String) -> impl Stream<Item = NetEvent> {
+    spawn_stream(run_client, address)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    Host,
+    Client,
+}
+
+// The multiplayer side of the app. The host owns the real board, clients only replay the
+// moves it broadcasts
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub role: Role,
+    pub mode: Mode,
+    pub address: String,
+    pub connection: Option<Connection>,
+    pub player: usize,
+    pub peers: Vec<usize>,
+    pub cursors: HashMap<usize, Vec2>,
+    // Race progress of the other players
+    pub progress: HashMap<usize, Progress>,
+    // Every move of the current game, replayed to clients that join late
+    pub moves: Vec<Move>,
+}
+
+impl Session {
+    // A client's mode is replaced by the host's once it is welcomed
+    pub fn new(role: Role, mode: Mode, address: String) -> Self {
+        Self {
+            role,
+            mode,
+            address,
+            connection: None,
+            player: HOST,
+            peers: Vec::new(),
+            cursors: HashMap::new(),
+            progress: HashMap::new(),
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<NetEvent> {
+        let address = self.address.clone();
+
+        match self.role {
+            Role::Host => Subscription::run_with_id((self.role, address.clone()), host(address)),
+            Role::Client => Subscription::run_with_id((self.role, address.clone()), join(address)),
+        }
+    }
+
+    // A client only counts as connected once the host told it which player it is
+    pub fn is_connected(&self) -> bool {
+        match self.role {
+            Role::Host => self.connection.is_some(),
+            Role::Client => self.player != HOST,
+        }
+    }
+
+    // Racers play the same board, seeing where the others point would give cells away
+    pub fn shares_cursors(&self) -> bool {
+        self.mode == Mode::Coop
+    }
+
+    pub fn set_cursor(&mut self, player: usize, pos: Option<Vec2>) {
+        match pos {
+            Some(pos) => self.cursors.insert(player, pos),
+            None => self.cursors.remove(&player),
+        };
+    }
+
+    pub fn send(&self, packet: &Packet) {
+        if let Some(connection) = &self.connection {
+            connection.send(packet);
+        }
+    }
+
+    pub fn close(&self) {
+        if let Some(connection) = &self.connection {
+            connection.close();
+        }
+    }
+}
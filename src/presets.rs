@@ -0,0 +1,64 @@
+use crate::error::AppError;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const PRESETS_FILE: &str = "presets.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub size_x: usize,
+    pub size_y: usize,
+    pub bomb_count: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Presets {
+    pub presets: Vec<Preset>,
+}
+
+impl Presets {
+    pub fn load() -> Result<Self, AppError> {
+        let Some(path) = storage::config_path(PRESETS_FILE) else {
+            return Ok(Self::default());
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+
+        let presets = serde_json::from_str(&contents).map_err(|e| storage::set_aside(&path, e))?;
+
+        Ok(Self { presets })
+    }
+
+    fn save(&self) -> Result<(), AppError> {
+        self.write()
+            .map_err(|e| AppError::Save(format!("{}: {}", PRESETS_FILE, e)))
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        let Some(path) = storage::config_path(PRESETS_FILE) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(&self.presets)?)
+    }
+
+    // Saving under an existing name overwrites that preset
+    pub fn insert(&mut self, preset: Preset) -> Result<(), AppError> {
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), AppError> {
+        self.presets.retain(|p| p.name != name);
+        self.save()
+    }
+}
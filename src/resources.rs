@@ -1,8 +1,163 @@
+use crate::error::AppError;
+use crate::storage;
 use iced::widget::image::Handle;
 use include_dir::{Dir, include_dir};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::{fs, io};
 
 pub static RESOURCES: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources");
 
-pub fn get_image_handle(name: impl Into<String>) -> Handle {
-    Handle::from_bytes(RESOURCES.get_file(name.into()).unwrap().contents())
+pub const DEFAULT_SKIN: &str = "Default";
+
+const SKINS_DIR: &str = "skins";
+const MANIFEST_FILE: &str = "skin.toml";
+
+// The embedded sprites by id, which is their path like `flag.png` or `red_text/1.png`.
+// Skins can replace any of them
+fn sprites() -> Vec<(&'static str, &'static [u8])> {
+    fn walk(dir: &'static Dir<'static>, sprites: &mut Vec<(&'static str, &'static [u8])>) {
+        for file in dir.files() {
+            if let Some(id) = file.path().to_str()
+                && id.ends_with(".png")
+            {
+                sprites.push((id, file.contents()));
+            }
+        }
+        for dir in dir.dirs() {
+            walk(dir, sprites);
+        }
+    }
+
+    let mut sprites = Vec::new();
+    walk(&RESOURCES, &mut sprites);
+    sprites
+}
+
+// Newest modification time of a file, or of anything in a directory
+fn last_modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    let mut latest = metadata.modified().ok();
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).ok()?.flatten() {
+            latest = latest.max(last_modified(&entry.path()));
+        }
+    }
+    latest
+}
+
+// A skin is a directory under `skins` in the config directory, holding a `skin.toml` and
+// sprites named like the embedded ones. The manifest can point sprites at other files:
+//
+// [sprites]
+// "hidden.png" = "tiles/closed.png"
+// "red_text/0.png" = "digits/zero.png"
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Manifest {
+    sprites: HashMap<String, PathBuf>,
+}
+
+// Every sprite is decoded into a handle once, when the skin is loaded. Views clone the
+// handles, which keeps their id, so the renderer reuses the uploaded textures instead of
+// decoding the sprites again every frame
+#[derive(Debug, Clone)]
+pub struct Skin {
+    pub name: String,
+    dir: Option<PathBuf>,
+    handles: HashMap<&'static str, Handle>,
+    modified: Option<SystemTime>,
+}
+
+impl Default for Skin {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_SKIN.to_string(),
+            dir: None,
+            handles: sprites()
+                .into_iter()
+                .map(|(id, bytes)| (id, Handle::from_bytes(bytes)))
+                .collect(),
+            modified: None,
+        }
+    }
+}
+
+impl Skin {
+    pub fn load(name: &str) -> Result<Self, AppError> {
+        if name == DEFAULT_SKIN {
+            return Ok(Self::default());
+        }
+
+        let dir = storage::config_path(SKINS_DIR)
+            .map(|skins| skins.join(name))
+            .ok_or_else(|| AppError::InvalidFile(format!("{}: no config directory", name)))?;
+        let invalid = |path: &Path, e: &dyn std::fmt::Display| {
+            AppError::InvalidFile(format!("{}: {}", path.display(), e))
+        };
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest: Manifest = fs::read_to_string(&manifest_path)
+            .map_err(|e| invalid(&manifest_path, &e))
+            .and_then(|contents| {
+                toml::from_str(&contents).map_err(|e| invalid(&manifest_path, &e))
+            })?;
+
+        // Sprites the skin doesn't have fall back to the embedded ones
+        let mut handles = HashMap::new();
+        for (id, embedded) in sprites() {
+            let path = match manifest.sprites.get(id) {
+                Some(file) => dir.join(file),
+                None => dir.join(id),
+            };
+
+            let handle = match fs::read(&path) {
+                Ok(bytes) => Handle::from_bytes(bytes),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Handle::from_bytes(embedded),
+                Err(e) => return Err(invalid(&path, &e)),
+            };
+            handles.insert(id, handle);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            modified: last_modified(&dir),
+            dir: Some(dir),
+            handles,
+        })
+    }
+
+    // Names of the skins in the skins directory, after the embedded one
+    pub fn list() -> Vec<String> {
+        let mut names = storage::config_path(SKINS_DIR)
+            .and_then(|skins| fs::read_dir(skins).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().join(MANIFEST_FILE).is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        names.insert(0, DEFAULT_SKIN.to_string());
+        names
+    }
+
+    pub fn get_image_handle(&self, id: &str) -> Handle {
+        self.handles[id].clone()
+    }
+
+    // Whether a file of the skin changed since the last check, so skin authors see their
+    // changes without restarting
+    pub fn poll_changes(&mut self) -> bool {
+        let modified = self.dir.as_deref().and_then(last_modified);
+        std::mem::replace(&mut self.modified, modified) != modified
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.dir.is_none()
+    }
 }
@@ -0,0 +1,287 @@
+use crate::error::AppError;
+use crate::storage;
+use minesweeper_engine::Difficulty;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+const HISTORY_FILE: &str = "history.jsonl";
+const HISTOGRAM_BINS: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub bomb_count: usize,
+    pub won: bool,
+    pub time_ms: u64,
+    pub three_bv: usize,
+    pub clicks: usize,
+    // Unix timestamp of when the game ended
+    pub date: u64,
+}
+
+impl GameRecord {
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::new(self.size_x, self.size_y, self.bomb_count)
+    }
+}
+
+// Every finished game, oldest first. Stored as one JSON object per line so a game can be
+// appended without rewriting the file
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    pub records: Vec<GameRecord>,
+}
+
+impl History {
+    // A missing file is an empty history, a corrupt one is reported
+    pub fn load() -> Result<Self, AppError> {
+        let Some(contents) =
+            storage::data_path(HISTORY_FILE).and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return Ok(Self::default());
+        };
+
+        let records = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    AppError::InvalidFile(format!("{} line {}: {}", HISTORY_FILE, i + 1, e))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { records })
+    }
+
+    pub fn push(&mut self, record: GameRecord) -> Result<(), AppError> {
+        let saved = Self::append(&record);
+        self.records.push(record);
+
+        saved.map_err(|e| AppError::Save(format!("{}: {}", HISTORY_FILE, e)))
+    }
+
+    fn append(record: &GameRecord) -> std::io::Result<()> {
+        let line = serde_json::to_string(record)?;
+
+        let Some(path) = storage::data_path(HISTORY_FILE) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+
+    // None gives the statistics over every difficulty
+    pub fn statistics(&self, difficulty: Option<Difficulty>) -> Statistics {
+        let records = self
+            .records
+            .iter()
+            .filter(|r| difficulty.is_none_or(|d| r.difficulty() == d))
+            .collect::<Vec<_>>();
+
+        Statistics::from_records(&records)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    pub played: usize,
+    pub won: usize,
+    pub current_streak: usize,
+    pub best_streak: usize,
+    pub average_time_ms: Option<u64>,
+    pub median_time_ms: Option<u64>,
+    pub best_time_ms: Option<u64>,
+    pub histogram: Vec<HistogramBin>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistogramBin {
+    pub from_ms: u64,
+    pub to_ms: u64,
+    pub count: usize,
+}
+
+impl Statistics {
+    fn from_records(records: &[&GameRecord]) -> Self {
+        let mut stats = Statistics {
+            played: records.len(),
+            ..Statistics::default()
+        };
+
+        let mut streak = 0;
+        for record in records {
+            if record.won {
+                stats.won += 1;
+                streak += 1;
+                stats.best_streak = stats.best_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+        stats.current_streak = streak;
+
+        // Only won games have a meaningful completion time
+        let mut times = records
+            .iter()
+            .filter(|r| r.won)
+            .map(|r| r.time_ms)
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+
+        if times.is_empty() {
+            return stats;
+        }
+
+        stats.average_time_ms = Some(times.iter().sum::<u64>() / times.len() as u64);
+        stats.median_time_ms = Some(match times.len() % 2 {
+            0 => (times[times.len() / 2 - 1] + times[times.len() / 2]) / 2,
+            _ => times[times.len() / 2],
+        });
+        stats.best_time_ms = times.first().copied();
+        stats.histogram = histogram(&times);
+
+        stats
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        match self.played {
+            0 => 0.0,
+            played => self.won as f32 / played as f32 * 100.0,
+        }
+    }
+}
+
+// Splits sorted times into equally wide bins, rounded to whole seconds
+fn histogram(sorted_times: &[u64]) -> Vec<HistogramBin> {
+    let (Some(&min), Some(&max)) = (sorted_times.first(), sorted_times.last()) else {
+        return Vec::new();
+    };
+
+    let min = min / 1000 * 1000;
+    let width = ((max - min) / HISTOGRAM_BINS as u64 / 1000 + 1) * 1000;
+
+    let mut bins = (0..HISTOGRAM_BINS as u64)
+        .map(|i| HistogramBin {
+            from_ms: min + i * width,
+            to_ms: min + (i + 1) * width,
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for time in sorted_times {
+        let index = ((time - min) / width) as usize;
+        bins[index.min(HISTOGRAM_BINS - 1)].count += 1;
+    }
+
+    // Drop empty bins past the slowest time
+    while bins.last().is_some_and(|b| b.count == 0) {
+        bins.pop();
+    }
+
+    bins
+}
+
+pub fn format_time(time_ms: u64) -> String {
+    format!("{}.{:03}s", time_ms / 1000, time_ms % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(won: bool, time_ms: u64) -> GameRecord {
+        GameRecord {
+            size_x: 8,
+            size_y: 8,
+            bomb_count: 10,
+            won,
+            time_ms,
+            three_bv: 10,
+            clicks: 12,
+            date: 0,
+        }
+    }
+
+    #[test]
+    fn statistics_count_streaks_and_times() {
+        let records = [
+            record(true, 1000),
+            record(false, 500),
+            record(true, 3000),
+            record(true, 10_500),
+        ];
+        let stats = Statistics::from_records(&records.iter().collect::<Vec<_>>());
+
+        assert_eq!((stats.played, stats.won), (4, 3));
+        assert_eq!((stats.current_streak, stats.best_streak), (2, 2));
+        assert_eq!(stats.win_rate(), 75.0);
+        assert_eq!(stats.average_time_ms, Some(4833));
+        assert_eq!(stats.median_time_ms, Some(3000));
+        assert_eq!(stats.best_time_ms, Some(1000));
+    }
+
+    #[test]
+    fn median_of_an_even_count_is_the_middle_pair() {
+        let records = [record(true, 1000), record(true, 2000)];
+        let stats = Statistics::from_records(&records.iter().collect::<Vec<_>>());
+
+        assert_eq!(stats.median_time_ms, Some(1500));
+    }
+
+    #[test]
+    fn losses_have_no_times() {
+        let stats = Statistics::from_records(&[&record(false, 1000)]);
+
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.average_time_ms, None);
+        assert!(stats.histogram.is_empty());
+        assert_eq!(Statistics::default().win_rate(), 0.0);
+    }
+
+    #[test]
+    fn histogram_bins_whole_seconds() {
+        let bins = histogram(&[1000, 3000, 10_500]);
+
+        assert_eq!(
+            bins.iter()
+                .map(|bin| (bin.from_ms, bin.to_ms, bin.count))
+                .collect::<Vec<_>>(),
+            [
+                (1000, 3000, 1),
+                (3000, 5000, 1),
+                (5000, 7000, 0),
+                (7000, 9000, 0),
+                (9000, 11_000, 1),
+            ]
+        );
+        assert_eq!(histogram(&[4200]).len(), 1);
+        assert!(histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn statistics_filter_by_difficulty() {
+        let history = History {
+            records: vec![
+                record(true, 1000),
+                GameRecord {
+                    size_x: 30,
+                    size_y: 16,
+                    bomb_count: 99,
+                    ..record(false, 0)
+                },
+            ],
+        };
+
+        assert_eq!(history.statistics(None).played, 2);
+        assert_eq!(history.statistics(Some(Difficulty::EXPERT)).played, 1);
+        assert_eq!(history.statistics(Some(Difficulty::BEGINNER)).won, 1);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::error::AppError;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "minesweeper-iced";
+
+// User-edited files (presets, settings) live under the XDG config directory
+pub fn config_path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR).join(file_name))
+}
+
+// Files that grow with play (history, results) live under the XDG data directory
+pub fn data_path(file_name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_DIR).join(file_name))
+}
+
+// Renames a file that failed to load to `<name>.bak`. The defaults used instead are saved
+// over the original path, and a single typo shouldn't cost the rest of the file
+pub fn set_aside(path: &Path, reason: impl Display) -> AppError {
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    let backup = path.with_file_name(format!("{}.bak", name));
+
+    AppError::InvalidFile(match fs::rename(path, &backup) {
+        Ok(()) => format!("{}: {}. It was renamed to {}.bak", name, reason, name),
+        Err(e) => format!(
+            "{}: {}. Renaming it to {}.bak failed: {}",
+            name, reason, name, e
+        ),
+    })
+}
+
+// Unix timestamp in seconds
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
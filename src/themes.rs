@@ -0,0 +1,31 @@
+use iced::theme::Palette;
+use iced::{Theme, color};
+use std::sync::LazyLock;
+
+// Grey like the original game. Built once, `App::theme` is asked for it every frame
+static CLASSIC: LazyLock<Theme> = LazyLock::new(|| {
+    Theme::custom(
+        "Classic".to_string(),
+        Palette {
+            background: color!(0xc0c0c0),
+            text: color!(0x000000),
+            primary: color!(0x000080),
+            success: color!(0x008000),
+            danger: color!(0xff0000),
+        },
+    )
+});
+
+pub fn all() -> Vec<Theme> {
+    std::iter::once(CLASSIC.clone())
+        .chain(Theme::ALL.iter().cloned())
+        .collect()
+}
+
+// Themes are stored by the name they are listed under
+pub fn named(name: &str) -> Option<Theme> {
+    std::iter::once(&*CLASSIC)
+        .chain(Theme::ALL)
+        .find(|theme| theme.to_string() == name)
+        .cloned()
+}
@@ -0,0 +1,191 @@
+mod render;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
+use minesweeper_engine::{Board, BoardOptions, Difficulty, FirstClickPolicy, GameState, Vec2};
+use std::io::{self, Write};
+use std::time::Duration;
+
+const DIFFICULTIES: [Difficulty; 3] = [
+    Difficulty::BEGINNER,
+    Difficulty::INTERMEDIATE,
+    Difficulty::EXPERT,
+];
+
+enum Screen {
+    Menu { selected: usize },
+    Game,
+}
+
+struct App {
+    board: Board,
+    screen: Screen,
+    cursor: Vec2,
+    running: bool,
+}
+
+impl App {
+    fn new() -> Self {
+        Self {
+            board: new_board(Difficulty::BEGINNER),
+            screen: Screen::Menu { selected: 0 },
+            cursor: Vec2::new(0, 0),
+            running: true,
+        }
+    }
+
+    fn start(&mut self, difficulty: Difficulty) {
+        self.board = new_board(difficulty);
+        self.screen = Screen::Game;
+        self.cursor = Vec2::new(self.board.size_x() / 2, self.board.size_y() / 2);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.running = false;
+            return;
+        }
+
+        match self.screen {
+            Screen::Menu { selected } => self.handle_menu_key(key, selected),
+            Screen::Game => self.handle_game_key(key),
+        }
+    }
+
+    fn handle_menu_key(&mut self, key: KeyEvent, selected: usize) {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.screen = Screen::Menu {
+                    selected: selected.saturating_sub(1),
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.screen = Screen::Menu {
+                    selected: (selected + 1).min(DIFFICULTIES.len() - 1),
+                }
+            }
+            KeyCode::Char(c @ '1'..='3') => self.start(DIFFICULTIES[c as usize - '1' as usize]),
+            KeyCode::Enter | KeyCode::Char(' ') => self.start(DIFFICULTIES[selected]),
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            _ => {}
+        }
+    }
+
+    fn handle_game_key(&mut self, key: KeyEvent) {
+        let max_x = self.board.size_x() - 1;
+        let max_y = self.board.size_y() - 1;
+
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => self.cursor.x = self.cursor.x.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.cursor.x = (self.cursor.x + 1).min(max_x),
+            KeyCode::Up | KeyCode::Char('k') => self.cursor.y = self.cursor.y.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor.y = (self.cursor.y + 1).min(max_y),
+            KeyCode::Char(' ') | KeyCode::Enter => self.play(|board, pos| board.uncover(pos)),
+            KeyCode::Char('f') => self.play(|board, pos| board.toggle_flag(pos)),
+            KeyCode::Char('c') => self.play(|board, pos| board.chord(pos)),
+            KeyCode::Char('r') => self.start(self.board.difficulty()),
+            KeyCode::Char('n') => self.screen = Screen::Menu { selected: 0 },
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            _ => {}
+        }
+    }
+
+    // What the screen shows of the clock, it only needs drawing again when this changes
+    fn shown_seconds(&self) -> Option<u64> {
+        match self.screen {
+            Screen::Menu { .. } => None,
+            Screen::Game => Some(self.board.elapsed().as_secs()),
+        }
+    }
+
+    fn play(
+        &mut self,
+        action: impl FnOnce(&mut Board, &Vec2) -> Result<(), minesweeper_engine::BoardError>,
+    ) {
+        if self.board.state() != GameState::Playing {
+            return;
+        }
+
+        // The cursor is clamped to the board, so the move can't fail
+        let _ = action(&mut self.board, &self.cursor);
+    }
+}
+
+fn new_board(difficulty: Difficulty) -> Board {
+    let options = BoardOptions {
+        first_click: FirstClickPolicy::Safe,
+        ..BoardOptions::default()
+    };
+
+    Board::with_options(
+        difficulty.size_x,
+        difficulty.size_y,
+        difficulty.bomb_count,
+        options,
+    )
+    .expect("built in difficulties are always valid")
+}
+
+fn run(stdout: &mut io::Stdout) -> io::Result<()> {
+    let mut app = App::new();
+    let mut changed = true;
+    let mut shown_seconds = None;
+
+    while app.running {
+        // Only what changed is sent, which matters over slow connections like SSH
+        if changed {
+            render::draw(stdout, &app)?;
+        } else if app.shown_seconds() != shown_seconds {
+            render::draw_status(stdout, &app.board)?;
+        }
+        changed = false;
+        shown_seconds = app.shown_seconds();
+
+        // Wake up regularly so the timer keeps counting without input
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                app.handle_key(key);
+                changed = true;
+            }
+            // Lines drawn in place can wrap differently after a resize
+            Event::Resize(..) => {
+                execute!(stdout, terminal::Clear(terminal::ClearType::All))?;
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn restore_terminal() -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    stdout.flush()
+}
+
+fn main() -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    // A panic would otherwise leave the shell in raw mode on the alternate screen, with
+    // the panic message lost along with it
+    let panic_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        panic_hook(info);
+    }));
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = run(&mut stdout);
+
+    restore_terminal()?;
+    result
+}
@@ -0,0 +1,147 @@
+use crate::{App, DIFFICULTIES, Screen};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor::MoveTo, queue};
+use minesweeper_engine::{Board, Cell, CellState, CellType, GameState};
+use std::io::{self, Write};
+
+const HELP: &str = "arrows/hjkl move  space reveal  f flag  c chord  r restart  n new game  q quit";
+
+fn number_colour(count: u8) -> Color {
+    match count {
+        1 => Color::Blue,
+        2 => Color::Green,
+        3 => Color::Red,
+        4 => Color::DarkBlue,
+        5 => Color::DarkRed,
+        6 => Color::Cyan,
+        7 => Color::Magenta,
+        _ => Color::Grey,
+    }
+}
+
+fn cell_symbol(cell: &Cell) -> (char, Color) {
+    match cell.state() {
+        CellState::Hidden => ('#', Color::DarkGrey),
+        CellState::Flagged => ('F', Color::Red),
+        CellState::Questioned => ('?', Color::Yellow),
+        CellState::Uncovered => match cell.cell_type() {
+            CellType::Bomb if cell.is_exploded() => ('X', Color::Red),
+            CellType::Bomb => ('*', Color::White),
+            CellType::Safe => (
+                char::from(b'0' + cell.adjacent_bomb_count()),
+                number_colour(cell.adjacent_bomb_count()),
+            ),
+            CellType::Empty => ('.', Color::DarkGrey),
+        },
+    }
+}
+
+fn status_line(board: &Board) -> String {
+    let mines = board.bomb_count() as isize - board.flag_count() as isize;
+    let state = match board.state() {
+        GameState::Playing => "",
+        GameState::Won => "  You won!",
+        GameState::Lost => "  Boom!",
+    };
+
+    format!(
+        "Mines: {:<4} Time: {:>3}{}",
+        mines,
+        board.elapsed().as_secs(),
+        state
+    )
+}
+
+// Lines are written over the previous frame instead of clearing the screen first, which
+// would send the whole screen again and flicker over slow connections. Whatever is left of
+// the old line past the new one is cleared
+fn line(stdout: &mut io::Stdout, row: u16, text: impl std::fmt::Display) -> io::Result<()> {
+    queue!(
+        stdout,
+        MoveTo(0, row),
+        Print(text),
+        Clear(ClearType::UntilNewLine)
+    )
+}
+
+// Returns the number of rows drawn
+fn draw_menu(stdout: &mut io::Stdout, selected: usize) -> io::Result<u16> {
+    line(stdout, 0, "Minesweeper")?;
+    line(stdout, 1, "")?;
+
+    for (i, difficulty) in DIFFICULTIES.iter().enumerate() {
+        let marker = if i == selected { '>' } else { ' ' };
+
+        line(
+            stdout,
+            2 + i as u16,
+            format!(
+                "{} {}. {:<13} {}x{}, {} mines",
+                marker,
+                i + 1,
+                difficulty.name(),
+                difficulty.size_x,
+                difficulty.size_y,
+                difficulty.bomb_count
+            ),
+        )?;
+    }
+
+    let help = 3 + DIFFICULTIES.len() as u16;
+    line(stdout, help - 1, "")?;
+    line(stdout, help, "up/down select  enter start  q quit")?;
+    Ok(help + 1)
+}
+
+// Returns the number of rows drawn
+fn draw_game(stdout: &mut io::Stdout, app: &App) -> io::Result<u16> {
+    let board = &app.board;
+
+    line(stdout, 0, status_line(board))?;
+    line(stdout, 1, "")?;
+
+    for y in 0..board.size_y() {
+        queue!(stdout, MoveTo(0, 2 + y as u16))?;
+
+        for x in 0..board.size_x() {
+            let (symbol, colour) = cell_symbol(&board.cells()[x][y]);
+            let is_cursor = app.cursor.x == x && app.cursor.y == y;
+
+            if is_cursor {
+                queue!(stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(
+                stdout,
+                SetForegroundColor(colour),
+                Print(symbol),
+                ResetColor,
+                SetAttribute(Attribute::Reset),
+                Print(' ')
+            )?;
+        }
+        queue!(stdout, Clear(ClearType::UntilNewLine))?;
+    }
+
+    let help = 3 + board.size_y() as u16;
+    line(stdout, help - 1, "")?;
+    line(stdout, help, HELP)?;
+    Ok(help + 1)
+}
+
+// Just the mine count and timer, for the clock ticking over without any input
+pub fn draw_status(stdout: &mut io::Stdout, board: &Board) -> io::Result<()> {
+    line(stdout, 0, status_line(board))?;
+    stdout.flush()
+}
+
+pub fn draw(stdout: &mut io::Stdout, app: &App) -> io::Result<()> {
+    let rows = match app.screen {
+        Screen::Menu { selected } => draw_menu(stdout, selected)?,
+        Screen::Game => draw_game(stdout, app)?,
+    };
+
+    // Rows a taller previous screen left below this one
+    queue!(stdout, MoveTo(0, rows), Clear(ClearType::FromCursorDown))?;
+    stdout.flush()
+}